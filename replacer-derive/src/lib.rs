@@ -0,0 +1,71 @@
+//! Derive macro for the [replacer](https://docs.rs/replacer) crate.
+//!
+//! Use the macro through the re-export in the `replacer` crate, which is enabled with the
+//! `proc-macro` feature.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, parse_macro_input, Data, DeriveInput, Fields};
+
+/// Generate a `template_builder` method creating a `StringRule` for every field of a struct.
+///
+/// Each field is used as the name of the rule, and the value of the field converted with
+/// `ToString` is what the placeholder will be replaced with.
+#[proc_macro_derive(Template)]
+pub fn derive_template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "`Template` can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "`Template` can only be derived for structs",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let rules = fields
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .map(|field| {
+            let name = field.unraw().to_string();
+
+            quote! {
+                .rule(
+                    ::replacer::rule::StringRule::new(
+                        #name,
+                        &::std::string::ToString::to_string(&self.#field),
+                    )
+                    .expect("field names are valid rule names"),
+                )
+            }
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Create a template builder with a `StringRule` for every field.
+            pub fn template_builder(&self) -> ::replacer::TemplateBuilder {
+                ::replacer::TemplateBuilder::new()
+                    #(#rules)*
+            }
+        }
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,120 @@
+//! Procedural macros for the [replacer](https://docs.rs/replacer) crate.
+//!
+//! Use the macros through the re-exports in the `replacer` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::{parse_macro_input, ItemStruct, LitStr};
+
+/// Mark a struct as a placeholder struct that can be replaced with a `StructRule`.
+///
+/// The struct is kept as is so the template can be compiled. The name of the rule is added as
+/// the associated constant `TEMPLATE_RULE` and the rule can be created with the associated
+/// function `template_rule`.
+///
+/// ```rust
+/// #[replacer::template_struct(name = "replace_with_other_struct")]
+/// struct Point3D {
+///     x: i32,
+///     y: i32,
+///     z: i32,
+/// }
+/// # assert_eq!(Point3D::TEMPLATE_RULE, "replace_with_other_struct");
+/// ```
+#[proc_macro_attribute]
+pub fn template_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse::<LitStr>()?);
+
+            Ok(())
+        } else {
+            Err(meta.error("unsupported template_struct property, expected `name`"))
+        }
+    });
+    parse_macro_input!(attr with parser);
+
+    let item = parse_macro_input!(item as ItemStruct);
+    let name = match name {
+        Some(name) => name,
+        None => {
+            return syn::Error::new_spanned(
+                &item.ident,
+                "missing rule name, use `#[replacer::template_struct(name = \"..\")]`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let expanded = quote! {
+        #item
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The name of the `StructRule` replacing this template struct.
+            #[allow(dead_code)]
+            pub const TEMPLATE_RULE: &'static str = #name;
+
+            /// Create the `StructRule` replacing this template struct.
+            #[allow(dead_code)]
+            pub fn template_rule(
+                replace_with: &str,
+            ) -> ::replacer::__private::Result<::replacer::rule::StructRule> {
+                ::replacer::rule::StructRule::new(#name, replace_with)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Placeholder attribute that can be replaced with an `AttrRule`.
+///
+/// The attribute after the name of the rule is applied to the item so the template can be
+/// compiled: `#[replacer::rust_attr(name; derive(Debug);)]` becomes `#[derive(Debug)]`.
+///
+/// ```rust
+/// #[replacer::rust_attr(replace_with_attr; derive(Debug, Clone);)]
+/// struct Point;
+/// # assert_eq!(format!("{:?}", Point.clone()), "Point");
+/// ```
+#[proc_macro_attribute]
+pub fn rust_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let mut tokens = attr.clone().into_iter().collect::<Vec<_>>();
+
+    let is_placeholder = matches!(
+        tokens.as_slice(),
+        [TokenTree::Ident(_), TokenTree::Punct(separator), _, ..] if separator.as_char() == ';'
+    );
+    if !is_placeholder {
+        return syn::Error::new_spanned(
+            attr,
+            "expected a placeholder attribute, like `#[replacer::rust_attr(name; derive(Debug);)]`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Remove the name of the rule and the optional trailing semicolon
+    if matches!(tokens.last(), Some(TokenTree::Punct(end)) if end.as_char() == ';') {
+        tokens.pop();
+    }
+    let placeholder = tokens
+        .into_iter()
+        .skip(2)
+        .collect::<proc_macro2::TokenStream>();
+    let item = proc_macro2::TokenStream::from(item);
+
+    let expanded = quote! {
+        #[#placeholder]
+        #item
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::{
+    rule::{ExprRule, Rule, StringRule, StructRule, TypeRule},
+    TemplateBuilder,
+};
+
+/// Rule definitions that can be loaded from a configuration file.
+///
+/// Every section maps the placeholder names to what they will be replaced with.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RuleSet {
+    /// Definitions for [`StringRule`].
+    #[serde(default)]
+    string: BTreeMap<String, String>,
+    /// Definitions for [`TypeRule`].
+    #[serde(default, rename = "type")]
+    type_: BTreeMap<String, String>,
+    /// Definitions for [`ExprRule`].
+    #[serde(default)]
+    expr: BTreeMap<String, String>,
+    /// Definitions for [`StructRule`].
+    #[serde(default, rename = "struct")]
+    struct_: BTreeMap<String, String>,
+}
+
+impl RuleSet {
+    /// Add all rules to the builder.
+    pub(crate) fn add_to(self, builder: TemplateBuilder) -> Result<TemplateBuilder> {
+        let builder = add_section(builder, "string", self.string, StringRule::new)?;
+        let builder = add_section(builder, "type", self.type_, TypeRule::new)?;
+        let builder = add_section(builder, "expr", self.expr, ExprRule::new)?;
+        add_section(builder, "struct", self.struct_, StructRule::new)
+    }
+}
+
+/// Add a rule for every definition in a section.
+fn add_section<R, F>(
+    builder: TemplateBuilder,
+    section: &str,
+    definitions: BTreeMap<String, String>,
+    new_rule: F,
+) -> Result<TemplateBuilder>
+where
+    R: Rule + 'static,
+    F: Fn(&str, &str) -> Result<R>,
+{
+    definitions
+        .iter()
+        .try_fold(builder, |builder, (matches, replace_with)| {
+            builder
+                .try_rule(new_rule(matches, replace_with))
+                .with_context(|| format!("invalid rule `{}` in section `[{}]`", matches, section))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn toml_rule_set() -> Result<()> {
+        let rules: RuleSet =
+            toml::from_str("[string]\nreplace = \"world\"\n[type]\nreplace_type = \"i32\"\n")?;
+        let template = rules.add_to(TemplateBuilder::new())?.build();
+        assert_eq!(
+            template.apply("$$replace$$ <replacer::rust_type!(replace_type; String;)>")?,
+            "world <i32>"
+        );
+
+        let rules: RuleSet = toml::from_str("[expr]\n\"(\" = \"1 + 1\"\n")?;
+        assert_eq!(
+            rules
+                .add_to(TemplateBuilder::new())
+                .err()
+                .unwrap()
+                .to_string(),
+            "invalid rule `(` in section `[expr]`"
+        );
+
+        assert!(toml::from_str::<RuleSet>("[unknown]\nreplace = \"world\"\n").is_err());
+
+        Ok(())
+    }
+}
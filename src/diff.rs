@@ -0,0 +1,124 @@
+/// A changed region of a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The line number in the original template where the change starts, starting at 1.
+    pub line: usize,
+    /// The original lines, empty when lines are only added.
+    pub old: String,
+    /// The changed lines, empty when lines are only removed.
+    pub new: String,
+}
+
+/// Compare two strings line by line and return the changed regions.
+///
+/// This uses the longest common subsequence of the lines, the lines that are the same at the
+/// start and end are skipped first to keep it fast for small changes.
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<Hunk> {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+
+    // Skip the lines that are the same at the start and end
+    let prefix = old_lines
+        .iter()
+        .zip(new_lines.iter())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_lines = &old_lines[prefix..old_lines.len() - suffix];
+    let new_lines = &new_lines[prefix..new_lines.len() - suffix];
+
+    // Length of the longest common subsequence starting at every pair of lines
+    let width = new_lines.len() + 1;
+    let mut lengths = vec![0usize; (old_lines.len() + 1) * width];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lengths[i * width + j] = if old_lines[i] == new_lines[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut hunks = vec![];
+    let mut current: Option<(usize, Vec<&str>, Vec<&str>)> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() || j < new_lines.len() {
+        if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+            // The lines are the same, so the current hunk is finished
+            hunks.extend(current.take().map(into_hunk));
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let hunk = current.get_or_insert_with(|| (prefix + i + 1, vec![], vec![]));
+        if j == new_lines.len()
+            || (i < old_lines.len() && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+        {
+            hunk.1.push(old_lines[i]);
+            i += 1;
+        } else {
+            hunk.2.push(new_lines[j]);
+            j += 1;
+        }
+    }
+    hunks.extend(current.map(into_hunk));
+
+    hunks
+}
+
+/// Join the collected lines into a hunk.
+fn into_hunk((line, old, new): (usize, Vec<&str>, Vec<&str>)) -> Hunk {
+    Hunk {
+        line,
+        old: old.join("\n"),
+        new: new.join("\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        assert_eq!(diff_lines("a\nb\nc", "a\nb\nc"), vec![]);
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\nc"),
+            vec![Hunk {
+                line: 2,
+                old: "b".to_string(),
+                new: "x".to_string()
+            }]
+        );
+        assert_eq!(
+            diff_lines("a\nb\nc\nd", "x\nb\nc\nd\ne"),
+            vec![
+                Hunk {
+                    line: 1,
+                    old: "a".to_string(),
+                    new: "x".to_string()
+                },
+                Hunk {
+                    line: 5,
+                    old: "".to_string(),
+                    new: "e".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nc"),
+            vec![Hunk {
+                line: 2,
+                old: "b".to_string(),
+                new: "".to_string()
+            }]
+        );
+    }
+}
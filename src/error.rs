@@ -0,0 +1,23 @@
+use std::{error::Error, fmt};
+
+/// Errors that can occur while applying a [`crate::Template`].
+///
+/// These are wrapped in an [`anyhow::Error`], use `downcast_ref` to inspect them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// Applying the rules took longer than the allowed duration.
+    Timeout,
+    /// A rule panicked while the rules were applied in a separate thread.
+    Panicked,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Timeout => write!(f, "applying the template rules timed out"),
+            RuleError::Panicked => write!(f, "a rule panicked while applying the template"),
+        }
+    }
+}
+
+impl Error for RuleError {}
@@ -0,0 +1,53 @@
+use std::ops::Deref;
+
+use crate::Template;
+
+/// A [`Template`] with rules that are only created when they are used for the first time.
+///
+/// All methods of [`Template`] can be called on it directly.
+/// Create it with [`TemplateBuilder::build_lazy`](crate::TemplateBuilder::build_lazy), the
+/// rules added with [`TemplateBuilder::lazy_rule`](crate::TemplateBuilder::lazy_rule) are
+/// compiled the first time the template is applied.
+///
+/// ```rust
+/// # use replacer::{RuleSpec, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .lazy_rule(RuleSpec::Type {
+///         matches: "replace".to_string(),
+///         replace_with: "i32".to_string(),
+///     })
+///     .build_lazy();
+///
+/// assert_eq!(
+///     template.apply("let a = <replacer::rust_type!(replace; String;)>::new();")?,
+///     "let a = <i32>::new();"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LazyTemplate {
+    /// The template containing the lazy rules.
+    template: Template,
+}
+
+impl LazyTemplate {
+    /// Wrap a template containing lazy rules.
+    pub(crate) fn new(template: Template) -> Self {
+        Self { template }
+    }
+
+    /// Get the template.
+    pub fn get(&self) -> &Template {
+        &self.template
+    }
+}
+
+impl Deref for LazyTemplate {
+    type Target = Template;
+
+    fn deref(&self) -> &Template {
+        self.get()
+    }
+}
@@ -46,7 +46,7 @@
 //! # assert_eq!(some_type, "");
 //!
 //! let some_generic_type: Vec<replacer::rust_type!(replace_with_type_in_vec; i32;)> = vec![];
-//! # assert_eq!(some_generic_type, vec![]);
+//! # assert_eq!(some_generic_type, Vec::<i32>::new());
 //! ```
 //!
 //! ### [`rule::StructRule`]
@@ -55,28 +55,428 @@
 //! replacer::rust_struct!(replace_with_struct; Point{ x: i32, y: i32 };);
 //! ```
 //!
+//! With the `macros` feature a struct can also be marked with the `template_struct` attribute.
+//!
+//! ```rust,ignore
+//! #[replacer::template_struct(name = "replace_with_other_struct")]
+//! struct Point3D {
+//!     x: i32,
+//!     y: i32,
+//!     z: i32,
+//! }
+//! ```
+//!
+//! ### [`rule::EnumRule`]
+//!
+//! ```rust
+//! replacer::rust_enum!(replace_with_enum; Direction { Up, Down };);
+//! ```
+//!
 //! ### [`rule::ExprRule`]
 //!
 //! ```rust
 //! println!("1 + 1 = {}", replacer::rust_expr!(replace_with_expression; 1 + 2;));
 //! ```
+//!
+//! ### [`rule::TraitRule`]
+//!
+//! ```rust
+//! replacer::rust_trait!(replace_with_trait; trait Placeholder { fn foo(&self); });
+//! ```
+//!
+//! ### [`rule::ConstRule`]
+//!
+//! ```rust
+//! replacer::rust_const!(replace_with_const; PLACEHOLDER: usize = 0;);
+//! ```
+//!
+//! ### [`rule::StaticRule`]
+//!
+//! ```rust
+//! replacer::rust_static!(replace_with_static; PLACEHOLDER: usize = 0;);
+//! ```
+//!
+//! ### [`rule::ImplRule`]
+//!
+//! ```rust
+//! struct Point;
+//!
+//! replacer::rust_impl! {replace_with_impl; impl Point { fn new() -> Self { Point } }}
+//! ```
+//!
+//! ### [`rule::ModRule`]
+//!
+//! ```rust
+//! replacer::rust_mod!(replace_with_mod; placeholder_mod;);
+//! ```
+//!
+//! ### [`rule::UseRule`]
+//!
+//! ```rust
+//! replacer::rust_use!(replace_with_use; std::vec::Vec;);
+//! ```
+//!
+//! ### [`rule::FnRule`]
+//!
+//! ```rust
+//! trait Shape {
+//!     replacer::rust_fn! {replace_with_fn; fn area(&self) -> i32;}
+//! }
+//! ```
+//!
+//! ### [`rule::BlockRule`]
+//!
+//! ```rust
+//! let x = replacer::rust_block!(replace_with_block; { 1 + 1 };);
+//! # assert_eq!(x, 2);
+//! ```
+//!
+//! ### [`rule::FnBodyRule`]
+//!
+//! ```rust
+//! fn compute() -> i32 {
+//!     replacer::rust_fn_body!(replace_with_body; { 1 + 1 };)
+//! }
+//! # assert_eq!(compute(), 2);
+//! ```
+//!
+//! ### [`rule::LifetimeRule`]
+//!
+//! Macros aren't allowed in the position of a lifetime, so templates using this won't compile.
+//!
+//! ```rust,ignore
+//! struct Wrapper<replacer::rust_lifetime!(replace_with_lifetime; 'a;)>(&'a str);
+//! ```
+//!
+//! ### [`rule::AttrRule`]
+//!
+//! The placeholder is an attribute macro applying the attribute after the name, it's enabled
+//! with the `macros` feature.
+//!
+//! ```rust,ignore
+//! #[replacer::rust_attr(replace_with_attr; derive(Debug, Clone);)]
+//! struct Point;
+//! ```
+//!
+//! ### [`rule::WhereRule`]
+//!
+//! Macros aren't allowed in the position of a where clause, so templates using this won't compile.
+//!
+//! ```rust,ignore
+//! fn print<T>(value: T) replacer::rust_where!(replace_with_where; where T: Debug;) {}
+//! ```
 
+mod config;
+mod diff;
+mod error;
+mod lazy;
+mod line_endings;
+mod options;
+mod placeholder;
 pub mod rule;
+mod spec;
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    env, fmt,
+    fs::{self, File},
+    io::{BufWriter, Read, Write},
+    iter::FromIterator,
+    path::Path,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
-use rule::Rule;
+use config::RuleSet;
+pub use diff::Hunk;
+pub use error::RuleError;
+pub use lazy::LazyTemplate;
+use line_endings::{convert_line_endings, line_ending, LineEndingWriter};
+pub use options::TemplateOptions;
+pub use placeholder::{PlaceholderInfo, PlaceholderKind, UnresolvedPlaceholder};
+#[cfg(feature = "proc-macro")]
+pub use replacer_derive::Template;
+#[cfg(feature = "macros")]
+pub use replacer_macros::{rust_attr, template_struct};
+use rule::{Rule, StringRule, TypeRule};
+pub use spec::{RuleSpec, SerializableTemplate};
+
+/// Items used by the code generated by the procedural macros, not part of the public API.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow::Result;
+}
 
 /// Builder for the [`Template`] struct.
-#[derive(Default)]
+///
+/// The builder can be cloned to extend a base set of rules.
+///
+/// ```rust
+/// # use replacer::{rule::StringRule, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let base = TemplateBuilder::new().rule(StringRule::new("greeting", "Hello")?);
+///
+/// let template = base.clone().rule(StringRule::new("replace", "world")?).build();
+/// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "Hello world");
+///
+/// let template = base.rule(StringRule::new("replace", "there")?).build();
+/// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "Hello there");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone, Debug)]
 pub struct TemplateBuilder {
     rules: Vec<Box<dyn Rule>>,
+    options: TemplateOptions,
 }
 
 impl TemplateBuilder {
     /// Start building a new [`Template`] struct.
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self::with_capacity(0)
+    }
+
+    /// Start building a new [`Template`] struct with space for the amount of rules.
+    ///
+    /// This avoids reallocating when a lot of rules are added.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            rules: Vec::with_capacity(capacity),
+            options: TemplateOptions::default(),
+        }
+    }
+
+    /// Start building a new [`Template`] struct with a [`rule::StringRule`] for every
+    /// environment variable starting with the prefix.
+    ///
+    /// The prefix is removed from the name of the variable, so `TEMPLATE_FOO=bar` with the
+    /// prefix `TEMPLATE_` replaces `$$FOO$$` with `bar`. Variables that aren't valid unicode are
+    /// skipped.
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// std::env::set_var("TEMPLATE_REPLACE", "world");
+    ///
+    /// let template = TemplateBuilder::from_env("TEMPLATE_")?.build();
+    ///
+    /// assert_eq!(template.apply("Hello $$REPLACE$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env(prefix: &str) -> Result<Self> {
+        let mut variables = env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .filter_map(|(name, value)| Some((name.strip_prefix(prefix)?.to_string(), value)))
+            .collect::<Vec<_>>();
+        // Keep the order of the rules the same between runs
+        variables.sort();
+
+        variables
+            .iter()
+            .try_fold(Self::new(), |builder, (matches, replace_with)| {
+                builder.try_rule(StringRule::new(matches, replace_with))
+            })
+    }
+
+    /// Start building a new [`Template`] struct from pairs of a tagged name and a value.
+    ///
+    /// The name is prefixed with the kind of rule, like `string:name` for a
+    /// [`rule::StringRule`] or `type:name` for a [`rule::TypeRule`]. This makes it easy to
+    /// pass rules as command line arguments.
+    ///
+    /// The supported kinds are `string`, `type`, `expr`, `struct`, `enum`, `fn`, `fn_body`,
+    /// `trait`, `const`, `static`, `impl`, `mod`, `use`, `block`, `lifetime`, `attr`, `where`,
+    /// `line`, `section`, `version`, `path` and `literal`.
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::from_args(&[("string:foo", "bar"), ("type:T", "PathBuf")])?
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply("let $$foo$$ = <replacer::rust_type!(T; String;)>::new();")?,
+    ///     "let bar = <PathBuf>::new();"
+    /// );
+    ///
+    /// assert!(TemplateBuilder::from_args(&[("foo", "bar")]).is_err());
+    /// assert!(TemplateBuilder::from_args(&[("unknown:foo", "bar")]).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_args(pairs: &[(&str, &str)]) -> Result<Self> {
+        pairs
+            .iter()
+            .try_fold(Self::new(), |mut builder, (tagged_name, value)| {
+                let (kind, name) = match tagged_name.split_once(':') {
+                    Some(split) => split,
+                    None => bail!(
+                        "argument `{}` is missing the kind of rule, like `string:{}`",
+                        tagged_name,
+                        tagged_name
+                    ),
+                };
+
+                let rule: Box<dyn Rule> = match kind {
+                    "string" => Box::new(StringRule::new(name, value)?),
+                    "type" => Box::new(TypeRule::new(name, value)?),
+                    "expr" => Box::new(rule::ExprRule::new(name, value)?),
+                    "struct" => Box::new(rule::StructRule::new(name, value)?),
+                    "enum" => Box::new(rule::EnumRule::new(name, value)?),
+                    "fn" => Box::new(rule::FnRule::new(name, value)?),
+                    "fn_body" => Box::new(rule::FnBodyRule::new(name, value)?),
+                    "trait" => Box::new(rule::TraitRule::new(name, value)?),
+                    "const" => Box::new(rule::ConstRule::new(name, value)?),
+                    "static" => Box::new(rule::StaticRule::new(name, value)?),
+                    "impl" => Box::new(rule::ImplRule::new(name, value)?),
+                    "mod" => Box::new(rule::ModRule::new(name, value)?),
+                    "use" => Box::new(rule::UseRule::new(name, value)?),
+                    "block" => Box::new(rule::BlockRule::new(name, value)?),
+                    "lifetime" => Box::new(rule::LifetimeRule::new(name, value)?),
+                    "attr" => Box::new(rule::AttrRule::new(name, value)?),
+                    "where" => Box::new(rule::WhereRule::new(name, value)?),
+                    "line" => Box::new(rule::LineRule::new(name, value)?),
+                    "section" => Box::new(rule::SectionRule::new(name, value)?),
+                    "version" => Box::new(rule::VersionRule::new(name, value)?),
+                    "path" => Box::new(rule::PathRule::new(name, value)?),
+                    "literal" => Box::new(rule::LiteralRule::new(name, value)?),
+                    _ => bail!(
+                        "unknown kind of rule `{}` in argument `{}`",
+                        kind,
+                        tagged_name
+                    ),
+                };
+                builder.rules.push(rule);
+
+                Ok(builder)
+            })
+    }
+
+    /// Start building a new [`Template`] struct with the rules defined in a TOML file.
+    ///
+    /// The sections `[string]`, `[type]`, `[expr]` and `[struct]` map the placeholder names
+    /// to what they will be replaced with, using the rule of the same name.
+    ///
+    /// ```toml
+    /// [string]
+    /// replace_with_world = "world"
+    ///
+    /// [type]
+    /// replace_with_type = "std::path::PathBuf"
+    /// ```
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let path = std::env::temp_dir().join("replacer_from_toml.toml");
+    /// # std::fs::write(&path, "[string]\nreplace = \"world\"\n")?;
+    /// let template = TemplateBuilder::from_toml(&path)?.build();
+    ///
+    /// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_toml(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading rules file {}", path.display()))?;
+        let rules: RuleSet = toml::from_str(&contents)
+            .with_context(|| format!("parsing rules file {}", path.display()))?;
+
+        rules.add_to(Self::new())
+    }
+
+    /// Start building a new [`Template`] struct with the rules defined in a JSON file.
+    ///
+    /// The objects `"string"`, `"type"`, `"expr"` and `"struct"` map the placeholder names
+    /// to what they will be replaced with, see [`TemplateBuilder::from_toml`].
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let path = std::env::temp_dir().join("replacer_from_json.json");
+    /// # std::fs::write(&path, r#"{ "string": { "replace": "world" } }"#)?;
+    /// let template = TemplateBuilder::from_json(&path)?.build();
+    ///
+    /// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading rules file {}", path.display()))?;
+
+        Self::from_json_str(&contents)
+            .with_context(|| format!("parsing rules file {}", path.display()))
+    }
+
+    /// Start building a new [`Template`] struct with the rules defined in a JSON string.
+    ///
+    /// See [`TemplateBuilder::from_json`].
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::from_json_str(
+    ///     r#"{ "string": { "replace": "world" }, "type": { "replace_with_type": "i32" } }"#,
+    /// )?
+    /// .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply("Hello $$replace$$ <replacer::rust_type!(replace_with_type; String;)>")?,
+    ///     "Hello world <i32>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        let rules: RuleSet = serde_json::from_str(json)?;
+
+        rules.add_to(Self::new())
+    }
+
+    /// Start building a new [`Template`] struct with the rules defined in a YAML file.
+    ///
+    /// The mappings `string`, `type`, `expr` and `struct` map the placeholder names
+    /// to what they will be replaced with, see [`TemplateBuilder::from_toml`].
+    ///
+    /// ```yaml
+    /// string:
+    ///   replace_with_world: world
+    /// expr:
+    ///   replace_with_expression: |
+    ///     vec![
+    ///         1 + 1,
+    ///     ]
+    /// ```
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let path = std::env::temp_dir().join("replacer_from_yaml.yaml");
+    /// # std::fs::write(&path, "string:\n  replace: world\n")?;
+    /// let template = TemplateBuilder::from_yaml(&path)?.build();
+    ///
+    /// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading rules file {}", path.display()))?;
+        let rules: RuleSet = serde_yaml::from_str(&contents)
+            .with_context(|| format!("parsing rules file {}", path.display()))?;
+
+        rules.add_to(Self::new())
     }
 
     /// Add a new rule that can be applied in batch.
@@ -101,41 +501,1559 @@ impl TemplateBuilder {
         self
     }
 
-    /// Create the [`Template`] struct.
-    pub fn build(self) -> Template {
-        Template { rules: self.rules }
+    /// Add a new rule from its description, it's only created when it's used for the first
+    /// time.
+    ///
+    /// The regexes of the rule are compiled when the template is applied instead of now, see
+    /// [`rule::LazyRule`]. Errors in the description are returned when the template is applied.
+    ///
+    /// ```rust
+    /// # use replacer::{RuleSpec, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .lazy_rule(RuleSpec::Type {
+    ///         matches: "replace".to_string(),
+    ///         replace_with: "i32".to_string(),
+    ///     })
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply("let a = <replacer::rust_type!(replace; String;)>::new();")?,
+    ///     "let a = <i32>::new();"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lazy_rule(self, spec: RuleSpec) -> Self {
+        self.rule(rule::LazyRule::new(spec))
     }
-}
 
-/// Internal representation of the template file.
-///
-/// Use [`TemplateBuilder`] to instaniate a new Template.
-///
-/// ```rust
-/// # use replacer::{rule::StringRule, TemplateBuilder};
-/// # fn main() -> anyhow::Result<()> {
-/// let template = TemplateBuilder::new()
-///     .rule(StringRule::new("replace", "world")?)
-///     .build();
-///
-/// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
-/// # Ok(())
-/// # }
-/// ```
-pub struct Template {
-    rules: Vec<Box<dyn Rule>>,
-}
+    /// Add a new rule from the result of a fallible rule constructor.
+    ///
+    /// This allows the builder to be chained without unwrapping every rule first.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .try_rule(StringRule::new("replace", "world"))?
+    ///     .try_rule(TypeRule::new("replace_with_type", "PathBuf"))?
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_rule<R>(self, rule: Result<R>) -> Result<Self>
+    where
+        R: Rule + 'static,
+    {
+        Ok(self.rule(rule?))
+    }
 
-impl Template {
-    /// Apply all rules sequentially or return the first error.
-    pub fn apply(&self, code: &str) -> Result<String> {
-        self.rules
-            .iter()
-            .fold(Ok(code.to_string()), |code, rule| match code {
-                // Apply the rule and return the string if there are no errors
-                Ok(code) => rule.convert(&code),
-                // Propagate errors further
-                Err(err) => Err(err),
+    /// Add multiple boxed rules, they will be applied in order.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{Rule, StringRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rules = [("greeting", "Hello"), ("replace", "world")]
+    ///     .iter()
+    ///     .map(|(name, value)| Ok(Box::new(StringRule::new(name, value)?) as Box<dyn Rule>))
+    ///     .collect::<anyhow::Result<Vec<_>>>()?;
+    ///
+    /// let template = TemplateBuilder::new().rules_from_iter(rules).build();
+    ///
+    /// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rules_from_iter<I>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = Box<dyn Rule>>,
+    {
+        self.rules.extend(iter);
+
+        self
+    }
+
+    /// Append the rules of another builder, they will be applied after the rules of this builder.
+    ///
+    /// The other options of this builder are kept.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let base = TemplateBuilder::new().rule(StringRule::new("greeting", "$$replace$$")?);
+    /// let extension = TemplateBuilder::new().rule(StringRule::new("replace", "world")?);
+    ///
+    /// let template = base.merge(extension).build();
+    ///
+    /// assert_eq!(template.apply("Hello $$greeting$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge(mut self, other: TemplateBuilder) -> Self {
+        self.rules.extend(other.rules);
+
+        self
+    }
+
+    /// Remove the first rule with the name, see [`rule::Rule::name`].
+    ///
+    /// Nothing happens when no rule has the name.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let defaults = TemplateBuilder::new()
+    ///     .rule(StringRule::new("greeting", "Hello")?)
+    ///     .rule(StringRule::new("replace", "world")?);
+    ///
+    /// let template = defaults.remove_rule_by_name("replace").build();
+    ///
+    /// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "Hello $$replace$$");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_rule_by_name(mut self, name: &str) -> Self {
+        if let Some(index) = self.rules.iter().position(|rule| rule.name() == name) {
+            self.rules.remove(index);
+        }
+
+        self
+    }
+
+    /// Add a [`rule::StringRule`] for every entry in the map.
+    ///
+    /// The keys are the placeholder names and the values what they will be replaced with. The
+    /// rules are added in the order of their names.
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut map = HashMap::new();
+    /// map.insert("greeting", "Hello");
+    /// map.insert("replace", "world");
+    ///
+    /// let template = TemplateBuilder::new().rules_from_map(&map)?.build();
+    ///
+    /// assert_eq!(template.rule_names().collect::<Vec<_>>(), ["greeting", "replace"]);
+    /// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rules_from_map(self, map: &HashMap<&str, &str>) -> Result<Self> {
+        // Keep the order of the rules the same between runs
+        let mut map = map.iter().collect::<Vec<_>>();
+        map.sort();
+
+        map.into_iter()
+            .try_fold(self, |builder, (matches, replace_with)| {
+                builder.try_rule(StringRule::new(matches, replace_with))
             })
     }
+
+    /// Add a [`rule::TypeRule`] for every entry in the map.
+    ///
+    /// The keys are the placeholder names and the values what they will be replaced with. The
+    /// rules are added in the order of their names.
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use replacer::TemplateBuilder;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut map = HashMap::new();
+    /// map.insert("replace_with_type", "PathBuf");
+    ///
+    /// let template = TemplateBuilder::new().type_rules_from_map(&map)?.build();
+    ///
+    /// assert_eq!(
+    ///     template.apply("<replacer::rust_type!(replace_with_type; String;)>::new()")?,
+    ///     "<PathBuf>::new()"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn type_rules_from_map(self, map: &HashMap<&str, &str>) -> Result<Self> {
+        // Keep the order of the rules the same between runs
+        let mut map = map.iter().collect::<Vec<_>>();
+        map.sort();
+
+        map.into_iter()
+            .try_fold(self, |builder, (matches, replace_with)| {
+                builder.try_rule(TypeRule::new(matches, replace_with))
+            })
+    }
+
+    /// Fail when placeholders are left after applying the template to a directory.
+    ///
+    /// By default a warning is printed for every file containing unresolved placeholders.
+    /// See [`Template::apply_dir`].
+    pub fn fail_on_unresolved(mut self, fail_on_unresolved: bool) -> Self {
+        self.options.fail_on_unresolved = fail_on_unresolved;
+
+        self
+    }
+
+    /// Replace all global settings of the template.
+    ///
+    /// See [`TemplateOptions`] for the available settings.
+    pub fn with_options(mut self, options: TemplateOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    /// The rules added so far, in the order they will be applied.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let builder = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(TypeRule::new("replace_with_type", "PathBuf")?);
+    ///
+    /// let names = builder.rules().iter().map(|rule| rule.name()).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["replace", "replace_with_type"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rules(&self) -> &[Box<dyn Rule>] {
+        &self.rules
+    }
+
+    /// Create the [`Template`] struct, failing when rules conflict with each other.
+    ///
+    /// Rules conflict when two rules of the same type match the same name, or when a
+    /// [`rule::StringRule`] and a [`rule::TypeRule`] match the same name.
+    /// Rules without a name are not checked.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let result = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(StringRule::new("replace", "there")?)
+    ///     .build_checked();
+    /// assert_eq!(
+    ///     result.err().unwrap().to_string(),
+    ///     "duplicate rules: StringRule 'replace'"
+    /// );
+    ///
+    /// let result = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(TypeRule::new("replace", "PathBuf")?)
+    ///     .build_checked();
+    /// assert_eq!(
+    ///     result.err().unwrap().to_string(),
+    ///     "both a StringRule and a TypeRule match: 'replace'"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_checked(self) -> Result<Template> {
+        let mut seen = HashSet::new();
+        let mut duplicates = vec![];
+        for rule in self.rules.iter().filter(|rule| rule.name() != "unknown") {
+            if !seen.insert((rule.rule_type(), rule.name())) {
+                duplicates.push(format!(
+                    "{} '{}'",
+                    short_type_name(rule.rule_type()),
+                    rule.name()
+                ));
+            }
+        }
+        if !duplicates.is_empty() {
+            bail!("duplicate rules: {}", duplicates.join(", "));
+        }
+
+        let string_rule = std::any::type_name::<StringRule>();
+        let type_rule = std::any::type_name::<TypeRule>();
+        let mut overlapping = seen
+            .iter()
+            .filter(|(rule_type, name)| {
+                *rule_type == string_rule && seen.contains(&(type_rule, name))
+            })
+            .map(|(_, name)| format!("'{}'", name))
+            .collect::<Vec<_>>();
+        overlapping.sort();
+        if !overlapping.is_empty() {
+            bail!(
+                "both a StringRule and a TypeRule match: {}",
+                overlapping.join(", ")
+            );
+        }
+
+        Ok(self.build())
+    }
+
+    /// Create the [`Template`] struct.
+    pub fn build(self) -> Template {
+        Template {
+            rules: Arc::new(self.rules),
+            options: self.options,
+        }
+    }
+
+    /// Create the [`Template`] struct wrapped in an [`Arc`], so it can be shared between threads.
+    ///
+    /// ```rust
+    /// # use std::thread;
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build_arc();
+    ///
+    /// let shared = template.clone();
+    /// let result = thread::spawn(move || shared.apply("Hello $$replace$$"))
+    ///     .join()
+    ///     .unwrap()?;
+    /// assert_eq!(result, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_arc(self) -> Arc<Template> {
+        Arc::new(self.build())
+    }
+
+    /// Create a [`LazyTemplate`], the rules are only compiled when the template is applied for
+    /// the first time.
+    ///
+    /// The template keeps the [`RuleSpec`] of every rule that has one, see
+    /// [`rule::Rule::to_spec`], instead of the compiled rule. Add the rules with
+    /// [`TemplateBuilder::lazy_rule`] so they aren't compiled when they're added either.
+    ///
+    /// This is useful when many templates are defined but only some of them are used.
+    ///
+    /// ```rust
+    /// # use replacer::{RuleSpec, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .lazy_rule(RuleSpec::Type {
+    ///         matches: "replace".to_string(),
+    ///         replace_with: "i32".to_string(),
+    ///     })
+    ///     .lazy_rule(RuleSpec::Regex {
+    ///         pattern: "(".to_string(),
+    ///         replace_with: "i32".to_string(),
+    ///     })
+    ///     .build_lazy();
+    ///
+    /// // The invalid rule is only noticed when it's used
+    /// assert_eq!(template.rule_names().collect::<Vec<_>>(), ["replace", "("]);
+    /// assert!(template
+    ///     .apply("let a = <replacer::rust_type!(replace; String;)>::new();")
+    ///     .is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_lazy(mut self) -> LazyTemplate {
+        self.rules = self
+            .rules
+            .into_iter()
+            .map(|rule| match rule.to_spec() {
+                Some(spec) => Box::new(rule::LazyRule::new(spec)),
+                None => rule,
+            })
+            .collect();
+
+        LazyTemplate::new(self.build())
+    }
+
+    /// Apply the rules once to the input without keeping the [`Template`] around.
+    ///
+    /// This is a shorthand for building a template with the default options and applying it.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{Rule, StringRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rules: Vec<Box<dyn Rule>> = vec![
+    ///     Box::new(StringRule::new("greeting", "Hello")?),
+    ///     Box::new(StringRule::new("replace", "world")?),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     TemplateBuilder::one_shot(rules, "$$greeting$$ $$replace$$")?,
+    ///     "Hello world"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn one_shot<I>(rules: I, input: &str) -> Result<String>
+    where
+        I: IntoIterator<Item = Box<dyn Rule>>,
+    {
+        let mut builder = Self::new();
+        builder.extend(rules);
+
+        builder.build().apply(input)
+    }
+}
+
+/// Add multiple rules at once.
+///
+/// ```rust
+/// # use replacer::{rule::{Rule, StringRule}, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let rules: Vec<Box<dyn Rule>> = vec![
+///     Box::new(StringRule::new("greeting", "Hello")?),
+///     Box::new(StringRule::new("replace", "world")?),
+/// ];
+///
+/// let mut builder = TemplateBuilder::new();
+/// builder.extend(rules);
+///
+/// assert_eq!(builder.build().apply("$$greeting$$ $$replace$$")?, "Hello world");
+/// # Ok(())
+/// # }
+/// ```
+impl Extend<Box<dyn Rule>> for TemplateBuilder {
+    fn extend<T: IntoIterator<Item = Box<dyn Rule>>>(&mut self, iter: T) {
+        self.rules.extend(iter);
+    }
+}
+
+/// Internal representation of the template file.
+///
+/// Use [`TemplateBuilder`] to instaniate a new Template.
+///
+/// ```rust
+/// # use replacer::{rule::StringRule, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .rule(StringRule::new("replace", "world")?)
+///     .build();
+///
+/// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
+///
+/// // The rules are shared between clones
+/// let cloned = template.clone();
+/// assert_eq!(cloned.apply("Hello $$replace$$")?, "Hello world");
+///
+/// // Debugging shows the rules
+/// assert!(format!("{:?}", template).starts_with(
+///     r#"Template { rules: [StringRule("replace" -> "world")], options: TemplateOptions {"#
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Template {
+    rules: Arc<Vec<Box<dyn Rule>>>,
+    options: TemplateOptions,
+}
+
+impl Template {
+    /// The global settings of the template.
+    pub fn options(&self) -> &TemplateOptions {
+        &self.options
+    }
+
+    /// The amount of rules in the template.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(template.rule_count(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the template doesn't contain any rules, applying it won't change anything.
+    ///
+    /// ```rust
+    /// # use replacer::TemplateBuilder;
+    /// assert!(TemplateBuilder::new().build().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The names of all rules in the order they are applied, see [`rule::Rule::name`].
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(TypeRule::new("replace_with_type", "PathBuf")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.rule_names().collect::<Vec<_>>(),
+    ///     vec!["replace", "replace_with_type"]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rule_names(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.name())
+    }
+
+    /// Apply all rules sequentially or return the first error.
+    ///
+    /// Rules that never change the template are skipped. A warning is logged when a suspicious
+    /// rule changes the template, see [`rule::Rule::is_suspicious`].
+    ///
+    /// When all lines of the code use the same line ending the result uses it too, so
+    /// replacements containing `\n` can be used for files with `\r\n` endings. The line
+    /// endings of code with mixed line endings aren't changed.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("body", "let a = 1;\nlet b = 2;")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply("fn main() {\r\n    $$body$$\r\n}")?,
+    ///     "fn main() {\r\n    let a = 1;\r\n    let b = 2;\r\n}"
+    /// );
+    ///
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("x", "1")?)
+    ///     .build();
+    ///
+    /// assert_eq!(template.apply("a\r\nb\nc\n$$x$$\r\n")?, "a\r\nb\nc\n1\r\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The error contains the byte offset of the first placeholder of the failing rule.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::ImplRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(ImplRule::new("point", "impl Point {}")?)
+    ///     .build();
+    ///
+    /// let error = template.apply("fn a() {}\nreplacer::rust_impl!(point; impl Point {").unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "rule 'point' failed on `replacer::rust_impl!(point; ..)` at byte 10"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply(&self, code: &str) -> Result<String> {
+        let mut output = Vec::with_capacity(code.len());
+        self.apply_writer(code, &mut output)?;
+
+        // Only `\r` bytes next to `\n` are changed, so the output is still valid UTF-8
+        Ok(String::from_utf8(output).expect("output of rules is not valid UTF-8"))
+    }
+
+    /// Apply a single rule of the template, logging a warning when it's suspicious.
+    fn apply_template_rule(&self, rule: &(dyn Rule + 'static), code: &str) -> Result<String> {
+        self.warn_if_suspicious(rule, code);
+
+        apply_rule(rule, code)
+    }
+
+    /// Log a warning when a suspicious rule changes the code.
+    fn warn_if_suspicious(&self, rule: &(dyn Rule + 'static), code: &str) {
+        if self.options.warnings && rule.is_suspicious() && rule.matches_any(code) {
+            log::warn!("suspicious rule {} is applied", rule);
+        }
+    }
+
+    /// Apply all rules sequentially, returning `None` when the code wasn't changed.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(template.try_apply("Hello $$replace$$")?, Some("Hello world".to_string()));
+    /// assert_eq!(template.try_apply("Hello world")?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_apply(&self, code: &str) -> Result<Option<String>> {
+        let result = self.apply(code)?;
+
+        Ok(if result == code { None } else { Some(result) })
+    }
+
+    /// Apply all rules sequentially, followed by a [`rule::StringRule`] for every entry in the
+    /// context.
+    ///
+    /// This avoids building a new template for substitutions that differ on every call.
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("greeting", "Hello")?)
+    ///     .build();
+    ///
+    /// let mut context = HashMap::new();
+    /// context.insert("replace", "world");
+    ///
+    /// assert_eq!(
+    ///     template.apply_with_context("$$greeting$$ $$replace$$", &context)?,
+    ///     "Hello world"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_with_context(&self, code: &str, context: &HashMap<&str, &str>) -> Result<String> {
+        let code = self.apply(code)?;
+
+        // Keep the order of the rules the same between runs
+        let mut context = context.iter().collect::<Vec<_>>();
+        context.sort();
+
+        context
+            .into_iter()
+            .try_fold(code, |code, (matches, replace_with)| {
+                apply_rule(&StringRule::new(matches, replace_with)?, &code)
+            })
+    }
+
+    /// Find all placeholder sites in a template, without applying any rules.
+    ///
+    /// This can be used to find out which rules are needed for a template.
+    ///
+    /// ```rust
+    /// # use replacer::{PlaceholderKind, Template};
+    /// let placeholders = Template::list_placeholders(
+    ///     "let a: replacer::rust_type!(replace_with_type; String;) = \"$$replace$$\".into();",
+    /// );
+    ///
+    /// assert_eq!(placeholders[0].kind, PlaceholderKind::Type);
+    /// assert_eq!(placeholders[0].name, "replace_with_type");
+    /// assert_eq!(placeholders[1].kind, PlaceholderKind::String);
+    /// assert_eq!(placeholders[1].location, (1, 60));
+    /// ```
+    pub fn list_placeholders(code: &str) -> Vec<PlaceholderInfo> {
+        placeholder::list_placeholders(code)
+    }
+
+    /// Apply all rules and return the placeholders that are still left in the result.
+    ///
+    /// This can be used to find placeholders for which no rule has been added.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let unresolved = template.validate("$$greeting$$ $$replace$$")?;
+    /// assert_eq!(unresolved.len(), 1);
+    /// assert_eq!(unresolved[0].name, "greeting");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self, code: &str) -> Result<Vec<UnresolvedPlaceholder>> {
+        let result = self.apply(code)?;
+
+        Ok(placeholder::find_placeholders(&result))
+    }
+
+    /// Apply all rules and fail when any placeholders are left in the result.
+    ///
+    /// The error lists the names and byte offsets of all unresolved placeholders.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(template.apply_checked("Hello $$replace$$")?, "Hello world");
+    /// assert_eq!(
+    ///     template.apply_checked("$$greeting$$ $$replace$$").unwrap_err().to_string(),
+    ///     "unresolved placeholders: `$$greeting$$` at byte 0"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_checked(&self, code: &str) -> Result<String> {
+        let result = self.apply(code)?;
+
+        let unresolved = placeholder::find_placeholders(&result);
+        if !unresolved.is_empty() {
+            bail!(
+                "unresolved placeholders: {}",
+                unresolved
+                    .iter()
+                    .map(|placeholder| placeholder.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Apply all rules and return the changed lines without returning the result.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, Hunk, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.dry_run("fn main() {\n    println!(\"Hello $$replace$$\");\n}")?,
+    ///     vec![Hunk {
+    ///         line: 2,
+    ///         old: "    println!(\"Hello $$replace$$\");".to_string(),
+    ///         new: "    println!(\"Hello world\");".to_string(),
+    ///     }]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dry_run(&self, code: &str) -> Result<Vec<Hunk>> {
+        let result = self.apply(code)?;
+
+        Ok(diff::diff_lines(code, &result))
+    }
+
+    /// Count how many placeholders would be replaced by applying the template.
+    ///
+    /// The matches of every rule are counted on the original code, replacements made by
+    /// earlier rules aren't taken into account.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(TypeRule::new("replace_type", "i32")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.count_replacements(
+    ///         "// $$replace$$\nlet a = <replacer::rust_type!(replace_type; u8;)>::default() + $$replace$$;"
+    ///     ),
+    ///     3
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_replacements(&self, code: &str) -> usize {
+        self.rules
+            .iter()
+            .filter(|rule| !rule.is_nop())
+            .map(|rule| rule.count_matches(code))
+            .sum()
+    }
+
+    /// Apply all rules and write the result to the output.
+    ///
+    /// The result of the last rule is written directly to the output instead of being
+    /// collected in a string first, see [`rule::Rule::convert_to_writer`].
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let mut output = vec![];
+    /// template.apply_writer("Hello $$replace$$", &mut output)?;
+    /// assert_eq!(output, b"Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_writer(&self, code: &str, output: &mut impl Write) -> Result<()> {
+        let mut output = LineEndingWriter::new(output, line_ending(code));
+
+        let rules = self
+            .rules
+            .iter()
+            .filter(|rule| !rule.is_nop())
+            .collect::<Vec<_>>();
+        match rules.split_last() {
+            Some((last, rules)) => {
+                let converted = rules.iter().try_fold(Cow::Borrowed(code), |code, rule| {
+                    self.apply_template_rule(rule.as_ref(), &code)
+                        .map(Cow::Owned)
+                })?;
+
+                // The result of the last rule is written directly to the output
+                self.warn_if_suspicious(last.as_ref(), &converted);
+                last.convert_to_writer(&converted, &mut output)
+                    .with_context(|| rule_error(last.as_ref(), &converted))?;
+            }
+            None => output
+                .write_all(code.as_bytes())
+                .context("writing template output")?,
+        }
+
+        output.finish().context("writing template output")
+    }
+
+    /// Apply all rules and append the result to an existing string.
+    ///
+    /// Nothing is appended when a rule fails.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let mut output = String::from("// Generated\n");
+    /// template.apply_to_string_writer("Hello $$replace$$", &mut output)?;
+    /// assert_eq!(output, "// Generated\nHello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_to_string_writer(&self, input: &str, output: &mut String) -> Result<()> {
+        let result = self.apply(input)?;
+        if output.is_empty() {
+            // Reuse the allocation of the result
+            *output = result;
+        } else {
+            output.push_str(&result);
+        }
+
+        Ok(())
+    }
+
+    /// Read the whole input, apply all rules and write the result to the output.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let mut output = vec![];
+    /// template.apply_reader(&mut "Hello $$replace$$".as_bytes(), &mut output)?;
+    /// assert_eq!(output, b"Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_reader(&self, input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+        let mut code = String::new();
+        input
+            .read_to_string(&mut code)
+            .context("reading template input")?;
+
+        self.apply_writer(&code, output)
+    }
+
+    /// Apply all rules to a token stream and parse the result as a token stream again.
+    ///
+    /// Converting a token stream to a string adds spaces between the tokens, so the
+    /// placeholders are joined again before the rules are applied.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("name", "world")?)
+    ///     .rule(TypeRule::new("replace_with_type", "Vec<u8>")?)
+    ///     .build();
+    ///
+    /// let tokens = "let $$name$$ = <replacer::rust_type!(replace_with_type; String;)>::new();"
+    ///     .parse()
+    ///     .unwrap();
+    /// let result = template.apply_to_token_stream(tokens)?;
+    /// assert_eq!(result.to_string(), "let world = < Vec < u8 > >:: new () ;");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "proc-macro2")]
+    pub fn apply_to_token_stream(
+        &self,
+        tokens: proc_macro2::TokenStream,
+    ) -> Result<proc_macro2::TokenStream> {
+        let code = join_placeholder_tokens(&tokens.to_string());
+        let result = self.apply(&code)?;
+
+        result
+            .parse()
+            .map_err(|err| anyhow::anyhow!("result is not a valid token stream: {}", err))
+    }
+
+    /// Apply all rules to a parsed Rust file and parse the result again.
+    ///
+    /// The file is pretty-printed before the rules are applied, parsing the result afterwards
+    /// catches replacements that create invalid Rust syntax.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("name", "world")?)
+    ///     .rule(TypeRule::new("replace_with_type", "Vec<u8>")?)
+    ///     .build();
+    ///
+    /// let file = syn::parse_file(
+    ///     r#"fn main() { let value = <replacer::rust_type!(replace_with_type; String;)>::new(); println!("Hello $$name$$"); }"#,
+    /// )?;
+    /// let result = template.apply_to_syn_file(&file)?;
+    /// assert_eq!(
+    ///     prettyplease::unparse(&result),
+    ///     "fn main() {\n    let value = <Vec<u8>>::new();\n    println!(\"Hello world\");\n}\n"
+    /// );
+    ///
+    /// let template = TemplateBuilder::new()
+    ///     .rule(TypeRule::new("replace_with_type", "Vec<")?)
+    ///     .build();
+    /// assert!(template.apply_to_syn_file(&file).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "syn")]
+    pub fn apply_to_syn_file(&self, file: &syn::File) -> Result<syn::File> {
+        let code = prettyplease::unparse(file);
+        let result = self.apply(&code)?;
+
+        syn::parse_file(&result).context("result is not valid Rust source code")
+    }
+
+    /// Apply all rules to multiple inputs.
+    ///
+    /// The results are in the same order as the inputs.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let results = template.apply_multiple(&["Hello $$replace$$", "Bye $$replace$$"]);
+    /// assert_eq!(results[0].as_ref().unwrap(), "Hello world");
+    /// assert_eq!(results[1].as_ref().unwrap(), "Bye world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_multiple(&self, inputs: &[&str]) -> Vec<Result<String>> {
+        #[cfg(feature = "parallel")]
+        if self.options.parallel {
+            return self.apply_multiple_par(inputs);
+        }
+
+        inputs.iter().map(|code| self.apply(code)).collect()
+    }
+
+    /// Apply all rules to multiple inputs in parallel.
+    ///
+    /// This is the parallel version of [`Template::apply_multiple`], the results are in the
+    /// same order as the inputs.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let results = template.apply_multiple_par(&["Hello $$replace$$", "Bye $$replace$$"]);
+    /// assert_eq!(results[0].as_ref().unwrap(), "Hello world");
+    /// assert_eq!(results[1].as_ref().unwrap(), "Bye world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn apply_multiple_par(&self, inputs: &[&str]) -> Vec<Result<String>> {
+        use rayon::prelude::*;
+
+        inputs.par_iter().map(|code| self.apply(code)).collect()
+    }
+
+    /// Apply all rules to multiple owned inputs in parallel, see
+    /// [`Template::apply_multiple_par`].
+    ///
+    /// The results are in the same order as the inputs.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let inputs = vec!["Hello $$replace$$".to_string(), "Bye $$replace$$".to_string()];
+    /// let results = template.apply_parallel(&inputs);
+    /// assert_eq!(results[0].as_ref().unwrap(), "Hello world");
+    /// assert_eq!(results[1].as_ref().unwrap(), "Bye world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn apply_parallel(&self, inputs: &[String]) -> Vec<Result<String>> {
+        let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+
+        self.apply_multiple_par(&inputs)
+    }
+
+    /// Apply all rules to every item of an iterator lazily.
+    ///
+    /// Every item is converted separately, so placeholders spanning multiple items, like a
+    /// multi-line `rust_struct!` invocation, won't be replaced. Use [`Template::apply`] on the
+    /// full string for those.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// let lines = template
+    ///     .apply_iter("Hello $$replace$$\nBye $$replace$$".lines())
+    ///     .collect::<anyhow::Result<Vec<_>>>()?;
+    /// assert_eq!(lines, vec!["Hello world", "Bye world"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_iter<'a, I>(&'a self, lines: I) -> impl Iterator<Item = Result<String>> + 'a
+    where
+        I: Iterator<Item = &'a str> + 'a,
+    {
+        lines.map(move |line| self.apply(line))
+    }
+
+    /// Apply all rules line by line, lines for which `skip` returns `true` are kept as is.
+    ///
+    /// The line passed to `skip` doesn't contain the line ending. Like with
+    /// [`Template::apply_iter`], placeholders spanning multiple lines won't be replaced.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply_lines(
+    ///         "Hello $$replace$$\n$$replace$$ // TEMPLATE_SKIP\n",
+    ///         |line| line.ends_with("// TEMPLATE_SKIP")
+    ///     )?,
+    ///     "Hello world\n$$replace$$ // TEMPLATE_SKIP\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_lines(&self, code: &str, skip: impl Fn(&str) -> bool) -> Result<String> {
+        let mut result = String::with_capacity(code.len());
+        for (index, line) in code.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if skip(content) {
+                result.push_str(line);
+            } else {
+                result.push_str(
+                    &self
+                        .apply(line)
+                        .with_context(|| format!("applying line {}", index + 1))?,
+                );
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Apply all rules sequentially in a separate thread, failing when it takes too long.
+    ///
+    /// When the timeout is exceeded [`RuleError::Timeout`] is returned, the spawned thread is
+    /// left running until it finishes because threads can't be killed. When a rule panics
+    /// [`RuleError::Panicked`] is returned.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use replacer::{rule::{Rule, StringRule}, RuleError, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     template.apply_with_timeout("Hello $$replace$$", Duration::from_secs(10))?,
+    ///     "Hello world"
+    /// );
+    ///
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "a\nb")?)
+    ///     .build();
+    /// let code = "q\r\n$$replace$$\r\n";
+    /// assert_eq!(
+    ///     template.apply_with_timeout(code, Duration::from_secs(10))?,
+    ///     template.apply(code)?
+    /// );
+    ///
+    /// #[derive(Clone)]
+    /// struct PanickingRule;
+    /// impl Rule for PanickingRule {
+    ///     fn convert(&self, _template: &str) -> anyhow::Result<String> {
+    ///         panic!("rule failed")
+    ///     }
+    /// }
+    ///
+    /// let template = TemplateBuilder::new().rule(PanickingRule).build();
+    /// let err = template
+    ///     .apply_with_timeout("Hello", Duration::from_secs(10))
+    ///     .unwrap_err();
+    /// assert_eq!(err.downcast_ref(), Some(&RuleError::Panicked));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_with_timeout(&self, code: &str, timeout: Duration) -> Result<String> {
+        // Cheap because the rules are shared
+        let template = self.clone();
+        let code = code.to_string();
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = template.apply(&code);
+
+            // The receiver is gone when the timeout has been exceeded
+            let _ = sender.send(result);
+        });
+
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(RuleError::Timeout.into()),
+            // The sender is dropped without sending when a rule panics
+            Err(RecvTimeoutError::Disconnected) => Err(RuleError::Panicked.into()),
+        }
+    }
+
+    /// Apply all rules sequentially, skipping the rules that return an error.
+    ///
+    /// This is useful when a partial result is better than nothing, for example when
+    /// generating a live preview. Like [`Template::apply`] the line endings of the code are kept.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{FailRule, StringRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(FailRule::new("not supported")?)
+    ///     .rule(StringRule::new("replace", "new\nworld")?)
+    ///     .build();
+    ///
+    /// assert_eq!(template.apply_ignore_errors("Hello $$replace$$"), "Hello new\nworld");
+    /// assert_eq!(
+    ///     template.apply_ignore_errors("Hello\r\n$$replace$$"),
+    ///     "Hello\r\nnew\r\nworld"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_ignore_errors(&self, code: &str) -> String {
+        let converted = self.rules.iter().filter(|rule| !rule.is_nop()).fold(
+            Cow::Borrowed(code),
+            |converted, rule| {
+                match self.apply_template_rule(rule.as_ref(), &converted) {
+                    // Use the converted string if the rule succeeded
+                    Ok(result) => Cow::Owned(result),
+                    // Keep the previous result when the rule failed
+                    Err(_) => converted,
+                }
+            },
+        );
+
+        convert_line_endings(&converted, line_ending(code))
+    }
+
+    /// Apply all rules sequentially, returning the unchanged code when a rule fails.
+    ///
+    /// Unlike [`Template::apply_ignore_errors`] no partial result is returned. A warning is
+    /// logged with the error when warnings are enabled, see [`TemplateOptions::warnings`].
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{FailRule, StringRule}, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    /// assert_eq!(template.apply_or_default("Hello $$replace$$"), "Hello world");
+    ///
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(FailRule::new("not supported")?)
+    ///     .build();
+    /// assert_eq!(template.apply_or_default("Hello $$replace$$"), "Hello $$replace$$");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_or_default(&self, code: &str) -> String {
+        self.apply(code).unwrap_or_else(|err| {
+            if self.options.warnings {
+                log::warn!(
+                    "applying template failed, keeping the original code: {:#}",
+                    err
+                );
+            }
+
+            code.to_string()
+        })
+    }
+
+    /// Apply all rules sequentially, returning every error instead of stopping at the first.
+    ///
+    /// Rules that fail are skipped, the following rules are applied to the result of the last
+    /// rule that succeeded.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::{Rule, StringRule}, TemplateBuilder};
+    /// #[derive(Clone)]
+    /// struct BrokenRule;
+    ///
+    /// impl Rule for BrokenRule {
+    ///     fn convert(&self, _template: &str) -> anyhow::Result<String> {
+    ///         anyhow::bail!("broken")
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let template = TemplateBuilder::new()
+    ///     .rule(BrokenRule)
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .rule(BrokenRule)
+    ///     .build();
+    ///
+    /// let errors = template.apply_collect_errors("Hello $$replace$$").unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_collect_errors(&self, code: &str) -> Result<String, Vec<anyhow::Error>> {
+        let mut errors = vec![];
+        let result = self.rules.iter().fold(code.to_string(), |code, rule| {
+            match apply_rule(rule.as_ref(), &code) {
+                Ok(converted) => converted,
+                Err(err) => {
+                    errors.push(err);
+
+                    code
+                }
+            }
+        });
+
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Read a file, apply all rules and write the result to another file.
+    ///
+    /// When both paths point to the same file the result is written to a temporary file first.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let dir = std::env::temp_dir();
+    /// # let input = dir.join("replacer_apply_to_file_input.rs");
+    /// # let output = dir.join("replacer_apply_to_file_output.rs");
+    /// # std::fs::write(&input, "Hello $$replace$$")?;
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// template.apply_to_file(&input, &output)?;
+    /// assert_eq!(std::fs::read_to_string(&output)?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_to_file(&self, input: &Path, output: &Path) -> Result<()> {
+        let code = fs::read_to_string(input)
+            .with_context(|| format!("reading template file {}", input.display()))?;
+
+        if is_same_file(input, output) {
+            // Write to a temporary file first so the source is never partially overwritten
+            let file_name = output
+                .file_name()
+                .with_context(|| format!("invalid output path {}", output.display()))?;
+            let mut temp_name = file_name.to_os_string();
+            temp_name.push(".replacer.tmp");
+            let temp = output.with_file_name(temp_name);
+
+            if let Err(err) = self.apply_to_new_file(&code, &temp) {
+                // Don't leave the temporary file behind
+                let _ = fs::remove_file(&temp);
+
+                return Err(err);
+            }
+            fs::rename(&temp, output)
+                .with_context(|| format!("replacing file {}", output.display()))?;
+        } else {
+            self.apply_to_new_file(&code, output)?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply all rules and write the result to a new or truncated file.
+    fn apply_to_new_file(&self, code: &str, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("creating output file {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        self.apply_writer(code, &mut writer)?;
+        writer
+            .flush()
+            .with_context(|| format!("writing output file {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Read a file, apply all rules and overwrite the file with the result.
+    ///
+    /// See [`Template::apply_to_file`].
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let path = std::env::temp_dir().join("replacer_apply_file_in_place.rs");
+    /// # std::fs::write(&path, "Hello $$replace$$")?;
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// template.apply_file_in_place(&path)?;
+    /// assert_eq!(std::fs::read_to_string(&path)?, "Hello world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_file_in_place(&self, path: &Path) -> Result<()> {
+        self.apply_to_file(path, path)
+    }
+
+    /// Recursively apply all rules to every `.rs` file in a directory.
+    ///
+    /// The results are written to the same relative paths in the output directory, other files
+    /// are copied verbatim. Files that still contain placeholders after applying the rules are
+    /// logged as warnings, unless [`TemplateBuilder::fail_on_unresolved`] is set, then an
+    /// error is returned. Symbolic links to directories are skipped.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::StringRule, TemplateBuilder};
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let input = std::env::temp_dir().join("replacer_apply_dir_input");
+    /// # let output = std::env::temp_dir().join("replacer_apply_dir_output");
+    /// # std::fs::create_dir_all(input.join("src"))?;
+    /// # std::fs::write(input.join("src/main.rs"), "Hello $$replace$$")?;
+    /// # std::fs::write(input.join("README.md"), "Hello $$replace$$")?;
+    /// # #[cfg(unix)]
+    /// # let _ = std::os::unix::fs::symlink(&input, input.join("src/cycle"));
+    /// let template = TemplateBuilder::new()
+    ///     .rule(StringRule::new("replace", "world")?)
+    ///     .build();
+    ///
+    /// template.apply_dir(&input, &output)?;
+    /// assert_eq!(std::fs::read_to_string(output.join("src/main.rs"))?, "Hello world");
+    /// assert_eq!(std::fs::read_to_string(output.join("README.md"))?, "Hello $$replace$$");
+    ///
+    /// // An output directory inside the input directory isn't walked itself
+    /// template.apply_dir(&input, &input.join("generated"))?;
+    /// assert!(!input.join("generated/generated").exists());
+    /// assert!(template.apply_dir(&input, &input).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_dir(&self, input: &Path, output: &Path) -> Result<()> {
+        if is_same_file(input, output) {
+            bail!(
+                "output directory {} is the same as the input directory",
+                output.display()
+            );
+        }
+
+        fs::create_dir_all(output)
+            .with_context(|| format!("creating directory {}", output.display()))?;
+
+        // The output directory can be inside the input directory, it must not be walked
+        let output_root = output
+            .canonicalize()
+            .with_context(|| format!("resolving directory {}", output.display()))?;
+
+        self.apply_dir_skipping(input, output, &output_root)
+    }
+
+    /// Recursively apply all rules to a directory, skipping the directory containing the
+    /// results.
+    fn apply_dir_skipping(&self, input: &Path, output: &Path, output_root: &Path) -> Result<()> {
+        fs::create_dir_all(output)
+            .with_context(|| format!("creating directory {}", output.display()))?;
+
+        let entries = fs::read_dir(input)
+            .with_context(|| format!("reading directory {}", input.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let output_path = output.join(path.file_name().unwrap_or_default());
+
+            // Doesn't follow symbolic links, unlike `Path::is_dir`
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("reading file type of {}", path.display()))?;
+            if file_type.is_dir() {
+                if !is_same_file(&path, output_root) {
+                    self.apply_dir_skipping(&path, &output_path, output_root)?;
+                }
+            } else if file_type.is_symlink() && path.is_dir() {
+                // Following it could recurse forever when the link points to a parent directory
+                if self.options.warnings {
+                    log::warn!("skipping symbolic link to directory {}", path.display());
+                }
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let code = fs::read_to_string(&path)
+                    .with_context(|| format!("reading template file {}", path.display()))?;
+                let result = self.apply(&code)?;
+
+                let unresolved = placeholder::find_placeholders(&result);
+                if !unresolved.is_empty() {
+                    let unresolved = unresolved
+                        .iter()
+                        .map(|placeholder| placeholder.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if self.options.fail_on_unresolved {
+                        bail!(
+                            "unresolved placeholders in {}: {}",
+                            path.display(),
+                            unresolved
+                        );
+                    }
+
+                    if self.options.warnings {
+                        log::warn!(
+                            "unresolved placeholders in {}: {}",
+                            path.display(),
+                            unresolved
+                        );
+                    }
+                }
+
+                fs::write(&output_path, result)
+                    .with_context(|| format!("writing output file {}", output_path.display()))?;
+            } else {
+                fs::copy(&path, &output_path)
+                    .with_context(|| format!("copying file {}", path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Show a summary of all rules in the order they are applied.
+///
+/// ```rust
+/// # use replacer::{rule::{StringRule, TypeRule}, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .rule(StringRule::new("foo", "bar")?)
+///     .rule(TypeRule::new("T", "PathBuf")?)
+///     .build();
+///
+/// assert_eq!(
+///     template.to_string(),
+///     r#"StringRule("foo" -> "bar"), TypeRule("T" -> "PathBuf")"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            rule.summary(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Build a template from the rules in an iterator.
+///
+/// ```rust
+/// # use replacer::{rule::{Rule, StringRule}, Template};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = ["greeting", "replace"]
+///     .iter()
+///     .map(|name| Ok(Box::new(StringRule::new(name, "word")?) as Box<dyn Rule>))
+///     .collect::<anyhow::Result<Template>>()?;
+///
+/// assert_eq!(template.apply("$$greeting$$ $$replace$$")?, "word word");
+/// # Ok(())
+/// # }
+/// ```
+impl FromIterator<Box<dyn Rule>> for Template {
+    fn from_iter<T: IntoIterator<Item = Box<dyn Rule>>>(iter: T) -> Self {
+        let mut builder = TemplateBuilder::new();
+        builder.extend(iter);
+
+        builder.build()
+    }
+}
+
+/// Apply a single rule, adding the name of the rule and the location of its first placeholder
+/// to the error.
+fn apply_rule(rule: &dyn Rule, code: &str) -> Result<String> {
+    rule.convert(code).with_context(|| rule_error(rule, code))
+}
+
+/// Describe a failing rule with the location of its first placeholder.
+fn rule_error(rule: &dyn Rule, code: &str) -> String {
+    match placeholder::find_placeholders(code)
+        .into_iter()
+        .find(|placeholder| placeholder.name == rule.name())
+    {
+        Some(placeholder) => format!("rule '{}' failed on {}", rule.name(), placeholder),
+        None => format!("rule '{}' failed", rule.name()),
+    }
+}
+
+/// Remove the spaces a token stream adds between the tokens of the placeholders.
+#[cfg(feature = "proc-macro2")]
+fn join_placeholder_tokens(code: &str) -> String {
+    static MACROS: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    static STRINGS: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+
+    let macros = MACROS.get_or_init(|| {
+        regex::Regex::new(r"replacer\s*::\s*(\w+)\s*!\s*([\(\[\{])\s*(\w+)\s*;")
+            .expect("invalid placeholder macro regex")
+    });
+    let strings = STRINGS.get_or_init(|| {
+        regex::Regex::new(r"\$\s*\$\s*(\w+)\s*\$\s*\$").expect("invalid placeholder string regex")
+    });
+
+    let code = macros.replace_all(code, "replacer::$1!$2$3;");
+    strings.replace_all(&code, "$$$$$1$$$$").into_owned()
+}
+
+/// Remove the module path from a type name.
+fn short_type_name(type_name: &str) -> &str {
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+/// Check whether two paths point to the same file, when the file doesn't exist yet they are compared directly.
+fn is_same_file(first: &Path, second: &Path) -> bool {
+    match (first.canonicalize(), second.canonicalize()) {
+        (Ok(first), Ok(second)) => first == second,
+        _ => first == second,
+    }
 }
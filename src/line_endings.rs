@@ -0,0 +1,126 @@
+use std::io::{self, Write};
+
+/// The line ending used by all lines of the code, `None` when the code doesn't contain any or
+/// mixes them.
+///
+/// Code with mixed line endings is left alone, because lines that weren't touched by any rule
+/// can't be told apart from the replacements.
+pub(crate) fn line_ending(code: &str) -> Option<&'static str> {
+    let crlf = code.matches("\r\n").count();
+    let lf = code.matches('\n').count() - crlf;
+
+    match (crlf, lf) {
+        (0, 0) => None,
+        (_, 0) => Some("\r\n"),
+        (0, _) => Some("\n"),
+        _ => None,
+    }
+}
+
+/// Convert all line endings of the text to `ending`, `None` doesn't convert anything.
+pub(crate) fn convert_line_endings(text: &str, ending: Option<&'static str>) -> String {
+    let mut output = Vec::with_capacity(text.len());
+    let mut writer = LineEndingWriter::new(&mut output, ending);
+    writer
+        .write_all(text.as_bytes())
+        .and_then(|()| writer.finish())
+        .expect("writing to a vector failed");
+
+    // Only `\r` bytes next to `\n` are changed, so the output is still valid UTF-8
+    String::from_utf8(output).expect("converted line endings are not valid UTF-8")
+}
+
+/// Writer converting the line endings of everything written to it.
+///
+/// [`LineEndingWriter::finish`] must be called after writing everything.
+pub(crate) struct LineEndingWriter<'a, W: Write + ?Sized> {
+    /// Where the converted output is written to.
+    inner: &'a mut W,
+    /// The line ending every line ending is converted to, `None` doesn't convert anything.
+    ending: Option<&'static str>,
+    /// Whether the last byte written was a `\r` that hasn't been passed on yet.
+    pending_cr: bool,
+}
+
+impl<'a, W: Write + ?Sized> LineEndingWriter<'a, W> {
+    /// Wrap a writer, converting all line endings to `ending`.
+    pub(crate) fn new(inner: &'a mut W, ending: Option<&'static str>) -> Self {
+        Self {
+            inner,
+            ending,
+            pending_cr: false,
+        }
+    }
+
+    /// Write the last `\r` when it wasn't part of a line ending.
+    pub(crate) fn finish(self) -> io::Result<()> {
+        if self.pending_cr {
+            self.inner.write_all(b"\r")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write + ?Sized> Write for LineEndingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let ending = match self.ending {
+            Some(ending) => ending,
+            None => {
+                self.inner.write_all(buf)?;
+
+                return Ok(buf.len());
+            }
+        };
+
+        let mut converted = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            // A `\r` is only kept when it isn't followed by a `\n`
+            if std::mem::take(&mut self.pending_cr) && byte != b'\n' {
+                converted.push(b'\r');
+            }
+
+            match byte {
+                b'\r' => self.pending_cr = true,
+                b'\n' => converted.extend_from_slice(ending.as_bytes()),
+                _ => converted.push(byte),
+            }
+        }
+        self.inner.write_all(&converted)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(chunks: &[&str], ending: Option<&'static str>) -> String {
+        let mut output = vec![];
+        let mut writer = LineEndingWriter::new(&mut output, ending);
+        for chunk in chunks {
+            writer.write_all(chunk.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(line_ending("a\r\nb\r\n"), Some("\r\n"));
+        assert_eq!(line_ending("a\nb\n"), Some("\n"));
+        assert_eq!(line_ending("a\r\nb\n"), None);
+        assert_eq!(line_ending("a"), None);
+
+        assert_eq!(convert(&["a\nb\r", "\nc\r"], Some("\r\n")), "a\r\nb\r\nc\r");
+        assert_eq!(convert(&["a\r\nb\r", "\nc\n"], Some("\n")), "a\nb\nc\n");
+        assert_eq!(convert(&["a\r\nb\n"], None), "a\r\nb\n");
+        assert_eq!(convert_line_endings("a\nb\r", Some("\r\n")), "a\r\nb\r");
+    }
+}
@@ -0,0 +1,113 @@
+//! Command line interface for applying replacer templates.
+//!
+//! ```sh
+//! replacer --rule-string name=World --rule-type T=PathBuf --input template.rs --output result.rs
+//! ```
+//!
+//! When no input or output file is passed the template is read from stdin and the result is
+//! written to stdout.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process,
+};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use replacer::TemplateBuilder;
+
+/// Apply replacer rules to a Rust source code template.
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    /// Replace a `$$KEY$$` string placeholder with VALUE.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    rule_string: Vec<(String, String)>,
+    /// Replace a `replacer::rust_type!(KEY; ...)` placeholder with VALUE.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    rule_type: Vec<(String, String)>,
+    /// Replace a `replacer::rust_expr!(KEY; ...)` placeholder with VALUE.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    rule_expr: Vec<(String, String)>,
+    /// Replace a `replacer::rust_struct!(KEY; ...)` placeholder with VALUE.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    rule_struct: Vec<(String, String)>,
+    /// Template file to read, stdin is used when omitted.
+    #[arg(long, short)]
+    input: Option<PathBuf>,
+    /// File to write the result to, stdout is used when omitted.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Split a `KEY=VALUE` argument on the first `=`.
+fn parse_key_value(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("`{}` must have the form KEY=VALUE", arg)),
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let tagged = [
+        ("string", &args.rule_string),
+        ("type", &args.rule_type),
+        ("expr", &args.rule_expr),
+        ("struct", &args.rule_struct),
+    ]
+    .iter()
+    .flat_map(|(kind, pairs)| {
+        pairs
+            .iter()
+            .map(move |(key, value)| (format!("{}:{}", kind, key), value.as_str()))
+    })
+    .collect::<Vec<_>>();
+    let pairs = tagged
+        .iter()
+        .map(|(key, value)| (key.as_str(), *value))
+        .collect::<Vec<_>>();
+
+    let template = TemplateBuilder::from_args(&pairs)?.build();
+
+    if let (Some(input), Some(output)) = (&args.input, &args.output) {
+        // Reads the whole input before writing, so the input file can also be the output file
+        return template.apply_to_file(input, output);
+    }
+
+    let mut input: Box<dyn io::Read> = match &args.input {
+        Some(path) => {
+            Box::new(File::open(path).with_context(|| format!("opening input file {:?}", path))?)
+        }
+        None => Box::new(io::stdin()),
+    };
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("creating output file {:?}", path))?,
+        )),
+        None => Box::new(io::stdout()),
+    };
+
+    template.apply_reader(&mut input, &mut output)?;
+    output.flush().context("writing template output")?;
+
+    Ok(())
+}
+
+fn main() {
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(err) if !err.use_stderr() => err.exit(),
+        Err(err) => {
+            // Print the usage error but keep the exit code consistent with the other errors
+            let _ = err.print();
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = run(args) {
+        eprintln!("error: {:#}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Global settings of a [`crate::Template`].
+///
+/// ```rust
+/// # use replacer::{rule::StringRule, TemplateBuilder, TemplateOptions};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .rule(StringRule::new("replace", "world")?)
+///     .with_options(TemplateOptions {
+///         fail_on_unresolved: true,
+///         ..TemplateOptions::default()
+///     })
+///     .build();
+///
+/// assert!(template.options().fail_on_unresolved);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplateOptions {
+    /// Fail when placeholders are left after applying the template to a directory.
+    ///
+    /// By default a warning is emitted for every file containing unresolved placeholders.
+    pub fail_on_unresolved: bool,
+    /// Apply the template to multiple inputs in parallel.
+    ///
+    /// This only has an effect when the `parallel` feature is enabled.
+    pub parallel: bool,
+    /// Emit warnings, for unresolved placeholders, suspicious rules and failures ignored by
+    /// [`crate::Template::apply_or_default`].
+    pub warnings: bool,
+}
+
+impl Default for TemplateOptions {
+    fn default() -> Self {
+        Self {
+            fail_on_unresolved: false,
+            parallel: false,
+            warnings: true,
+        }
+    }
+}
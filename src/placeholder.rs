@@ -0,0 +1,215 @@
+use std::{fmt, sync::OnceLock};
+
+use regex::Regex;
+
+/// A placeholder that is still present after applying all rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedPlaceholder {
+    /// Name of the placeholder, `replace` for both `$$replace$$` and `replacer::rust_type!(replace; ..)`.
+    pub name: String,
+    /// Name of the template macro, `None` for `$$..$$` placeholders.
+    pub macro_name: Option<String>,
+    /// Byte offset of the placeholder in the result.
+    pub offset: usize,
+}
+
+impl fmt::Display for UnresolvedPlaceholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.macro_name {
+            Some(macro_name) => write!(
+                f,
+                "`replacer::{}!({}; ..)` at byte {}",
+                macro_name, self.name, self.offset
+            ),
+            None => write!(f, "`$${}$$` at byte {}", self.name, self.offset),
+        }
+    }
+}
+
+/// The kind of a placeholder site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PlaceholderKind {
+    /// A `$$..$$` string placeholder.
+    String,
+    /// A `replacer::rust_type!` macro.
+    Type,
+    /// A `replacer::rust_expr!` macro.
+    Expr,
+    /// A `replacer::rust_struct!` macro.
+    Struct,
+    /// A `replacer::rust_enum!` macro.
+    Enum,
+    /// A `replacer::rust_fn!` macro.
+    Fn,
+    /// A `replacer::rust_trait!` macro.
+    Trait,
+    /// A `replacer::rust_const!` macro.
+    Const,
+    /// A `replacer::rust_static!` macro.
+    Static,
+    /// A `replacer::rust_impl!` macro.
+    Impl,
+    /// A `replacer::rust_mod!` macro.
+    Mod,
+    /// A `replacer::rust_use!` macro.
+    Use,
+    /// A `replacer::rust_lifetime!` macro.
+    Lifetime,
+    /// A `#[replacer::rust_attr(..)]` attribute.
+    Attr,
+    /// A `replacer::rust_block!` macro.
+    Block,
+    /// A `replacer::rust_fn_body!` macro.
+    FnBody,
+    /// A `replacer::rust_where!` macro.
+    Where,
+}
+
+impl PlaceholderKind {
+    /// Get the kind belonging to a template macro name.
+    fn from_macro_name(macro_name: &str) -> Option<Self> {
+        match macro_name {
+            "rust_type" => Some(PlaceholderKind::Type),
+            "rust_expr" => Some(PlaceholderKind::Expr),
+            "rust_struct" => Some(PlaceholderKind::Struct),
+            "rust_enum" => Some(PlaceholderKind::Enum),
+            "rust_fn" => Some(PlaceholderKind::Fn),
+            "rust_trait" => Some(PlaceholderKind::Trait),
+            "rust_const" => Some(PlaceholderKind::Const),
+            "rust_static" => Some(PlaceholderKind::Static),
+            "rust_impl" => Some(PlaceholderKind::Impl),
+            "rust_mod" => Some(PlaceholderKind::Mod),
+            "rust_use" => Some(PlaceholderKind::Use),
+            "rust_lifetime" => Some(PlaceholderKind::Lifetime),
+            "rust_attr" => Some(PlaceholderKind::Attr),
+            "rust_block" => Some(PlaceholderKind::Block),
+            "rust_fn_body" => Some(PlaceholderKind::FnBody),
+            "rust_where" => Some(PlaceholderKind::Where),
+            _ => None,
+        }
+    }
+}
+
+/// A placeholder site in a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderInfo {
+    /// What kind of rule is needed to replace the placeholder.
+    pub kind: PlaceholderKind,
+    /// Name of the placeholder, this is what rules match with.
+    pub name: String,
+    /// Line and column of the placeholder, both starting at 1.
+    pub location: (usize, usize),
+}
+
+/// Find all placeholder sites with their kind and location in the code.
+pub(crate) fn list_placeholders(code: &str) -> Vec<PlaceholderInfo> {
+    find_placeholders(code)
+        .into_iter()
+        .filter_map(|placeholder| {
+            let kind = match &placeholder.macro_name {
+                Some(macro_name) => PlaceholderKind::from_macro_name(macro_name)?,
+                None => PlaceholderKind::String,
+            };
+
+            let before = &code[..placeholder.offset];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+            let column = before[line_start..].chars().count() + 1;
+
+            Some(PlaceholderInfo {
+                kind,
+                name: placeholder.name,
+                location: (line, column),
+            })
+        })
+        .collect()
+}
+
+/// Find all placeholders in the code.
+pub(crate) fn find_placeholders(code: &str) -> Vec<UnresolvedPlaceholder> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r"\$\$(?P<string>\w+)(?::\w+)?\$\$|replacer::(?P<macro>rust_\w+)!?\s*[\(\{\[]\s*(?:pub(?:\s*\([^)]*\))?\s+)?(?P<name>\w+)\s*;",
+        )
+        .expect("invalid placeholder regex")
+    });
+
+    regex
+        .captures_iter(code)
+        .filter_map(|caps| {
+            let offset = caps.get(0)?.start();
+            match caps.name("string") {
+                Some(name) => Some(UnresolvedPlaceholder {
+                    name: name.as_str().to_string(),
+                    macro_name: None,
+                    offset,
+                }),
+                None => Some(UnresolvedPlaceholder {
+                    name: caps.name("name")?.as_str().to_string(),
+                    macro_name: Some(caps.name("macro")?.as_str().to_string()),
+                    offset,
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(
+            find_placeholders(
+                "Hello $$replace:upper$$!\nlet a = <replacer::rust_type!(replace_type; String;)>::new();\nreplacer::rust_struct! {pub(crate) point; Point { x: i32 };}"
+            ),
+            vec![
+                UnresolvedPlaceholder {
+                    name: "replace".to_string(),
+                    macro_name: None,
+                    offset: 6
+                },
+                UnresolvedPlaceholder {
+                    name: "replace_type".to_string(),
+                    macro_name: Some("rust_type".to_string()),
+                    offset: 34
+                },
+                UnresolvedPlaceholder {
+                    name: "point".to_string(),
+                    macro_name: Some("rust_struct".to_string()),
+                    offset: 87
+                },
+            ]
+        );
+        assert_eq!(find_placeholders("Hello world!"), vec![]);
+    }
+
+    #[test]
+    fn placeholder_info() {
+        assert_eq!(
+            list_placeholders(
+                "Hello $$replace$$!\nlet a = <replacer::rust_type!(replace_type; String;)>::new();\n  replacer::rust_enum! {pub shape; Shape { A };}"
+            ),
+            vec![
+                PlaceholderInfo {
+                    kind: PlaceholderKind::String,
+                    name: "replace".to_string(),
+                    location: (1, 7)
+                },
+                PlaceholderInfo {
+                    kind: PlaceholderKind::Type,
+                    name: "replace_type".to_string(),
+                    location: (2, 10)
+                },
+                PlaceholderInfo {
+                    kind: PlaceholderKind::Enum,
+                    name: "shape".to_string(),
+                    location: (3, 3)
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
+
+/// Replace a Rust attribute, both in inner `#![..]` and outer `#[..]` position.
+///
+/// The placeholder is the [`crate::rust_attr`] attribute, the older `replacer::rust_attr!(..)`
+/// form is also matched.
+/// ```rust
+/// # use replacer::rule::{Rule, AttrRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = AttrRule::new("derive_rule", "derive(Debug, Clone, PartialEq)")?;
+/// assert_eq!(rule.convert("#[replacer::rust_attr(derive_rule; derive(Debug, Clone);)]")?,
+///     "#[derive(Debug, Clone, PartialEq)]");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AttrRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for AttrRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!("{}{}]", &caps["open"], replace_with)
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Attr {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for AttrRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AttrRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl AttrRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"(?P<open>#!?\[)\s*replacer::rust_attr!?\s*[\({{]{};{};?\s*[\)}}]\s*\]",
+            matches,
+            tokens_pattern()
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn attr_rule() -> Result<()> {
+        assert_eq!(
+            AttrRule::new("replace", "derive(Debug)")?
+                .convert("#[replacer::rust_attr!(replace; derive(Clone);)]\nstruct Point;")?,
+            "#[derive(Debug)]\nstruct Point;"
+        );
+        assert_eq!(
+            AttrRule::new("replace", "allow(dead_code)")?
+                .convert("#![replacer::rust_attr! {replace; deny(warnings);}]")?,
+            "#![allow(dead_code)]"
+        );
+        assert_eq!(
+            AttrRule::new("replace", "repr(C)")?
+                .convert("#[ replacer::rust_attr!(replace; cfg_attr(test, derive(Debug));) ]")?,
+            "#[repr(C)]"
+        );
+        assert_eq!(
+            AttrRule::new("replace", "derive(Debug)")?
+                .convert("#[replacer::rust_attr(replace; derive(Clone))]\nstruct Point;")?,
+            "#[derive(Debug)]\nstruct Point;"
+        );
+        assert_eq!(
+            AttrRule::new("replace", "derive(Debug)")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
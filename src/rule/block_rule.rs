@@ -0,0 +1,146 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{rule::find_macro_invocations, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust block expression with a placeholder block that can be
+/// compiled.
+///
+/// ```rust
+/// let x = replacer::rust_block!(replace_with_block; { let x = 0; x };);
+/// # assert_eq!(x, 0);
+/// ```
+#[macro_export]
+macro_rules! rust_block {
+    ($_name:ident; $placeholder:block;) => {
+        $placeholder
+    };
+}
+
+/// Replace a Rust block expression.
+///
+/// The replacement is inserted verbatim, so it should contain the braces of the block.
+/// Because blocks can contain any code the macro is found by balancing the delimiters.
+/// ```rust
+/// # use replacer::rule::{Rule, BlockRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = BlockRule::new("init_block", "{ let x = compute(); x }")?;
+/// assert_eq!(rule.convert("let x = replacer::rust_block!(init_block; { let x = 0; x };);")?,
+///     "let x = { let x = compute(); x };");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BlockRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+}
+
+impl Rule for BlockRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let ranges = find_macro_invocations(template, "rust_block", &self.matches)?;
+
+        let mut result = String::with_capacity(template.len());
+        let mut last_end = 0;
+        for range in ranges {
+            result.push_str(&template[last_end..range.start]);
+            result.push_str(&self.replace_with);
+            last_end = range.end;
+        }
+        result.push_str(&template[last_end..]);
+
+        Ok(result)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        // An error means an invocation has been found but it's not balanced
+        find_macro_invocations(template, "rust_block", &self.matches)
+            .map_or(true, |ranges| !ranges.is_empty())
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        // An unbalanced invocation is still counted, applying the rule will fail on it
+        find_macro_invocations(template, "rust_block", &self.matches)
+            .map_or(1, |ranges| ranges.len())
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Block {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for BlockRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BlockRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl BlockRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn block_rule() -> Result<()> {
+        assert_eq!(
+            BlockRule::new("replace", "{ 2 }")?.convert(
+                "let a = replacer::rust_block!(replace; { let b = { 1 }; if b > 0 { b } else { 0 } };);"
+            )?,
+            "let a = { 2 };"
+        );
+        assert_eq!(
+            BlockRule::new("replace", "{ 2 }")?
+                .convert("let a = replacer::rust_block!(other; { 1 };) + replacer::rust_block! {replace; { 1 };};")?,
+            "let a = replacer::rust_block!(other; { 1 };) + { 2 };"
+        );
+        assert_eq!(
+            BlockRule::new("replace", "{ 2 }")?
+                .convert("let a = replacer::rust_block!(replace; { let b = '{'; b };); // rest")?,
+            "let a = { 2 }; // rest"
+        );
+        assert!(BlockRule::new("replace", "{ 2 }")?
+            .convert("replacer::rust_block!(replace; { 1 };")
+            .is_err());
+        assert_eq!(
+            BlockRule::new("replace", "{ 2 }")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+        assert_eq!(
+            BlockRule::new("replace", "{ 2 }")?.count_matches(
+                "replacer::rust_block!(replace; { 1 };) + replacer::rust_block!(other; { 1 };) + replacer::rust_block!(replace; { 1 };)"
+            ),
+            2
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{Rule, RuleSpec};
+
+/// Apply multiple rules sequentially as a single rule.
+///
+/// This can be used to bundle rules that belong together.
+/// ```rust
+/// # use replacer::{rule::{ChainRule, StringRule, TypeRule}, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let point = ChainRule::new(vec![
+///     Box::new(StringRule::new("point", "Point2D")?),
+///     Box::new(TypeRule::new("point", "Point2D")?),
+/// ])?;
+///
+/// let template = TemplateBuilder::new().rule(point).build();
+/// assert_eq!(
+///     template.apply("// $$point$$\nlet p = <replacer::rust_type!(point; Point;)>::new();")?,
+///     "// Point2D\nlet p = <Point2D>::new();"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ChainRule {
+    /// Rules that will be applied in order.
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Rule for ChainRule {
+    fn name(&self) -> &str {
+        // The rules of a chain usually belong to the same placeholder
+        self.rules.first().map_or("unknown", |rule| rule.name())
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        self.rules
+            .iter()
+            .try_fold(template.to_string(), |template, rule| {
+                rule.convert(&template)
+            })
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Chain {
+            rules: self
+                .rules
+                .iter()
+                .map(|rule| rule.to_spec())
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ChainRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChainRule([")?;
+        for (index, rule) in self.rules.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            rule.summary(f)?;
+        }
+        write!(f, "])")
+    }
+}
+
+impl ChainRule {
+    /// Setup a new rule.
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Result<Self> {
+        Ok(Self { rules })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::rule::StringRule;
+
+    #[test]
+    fn chain_rule() -> Result<()> {
+        let rule = ChainRule::new(vec![
+            Box::new(StringRule::new("first", "$$second$$")?),
+            Box::new(StringRule::new("second", "world")?),
+        ])?;
+        assert_eq!(rule.convert("Hello $$first$$!")?, "Hello world!");
+        assert_eq!(rule.name(), "first");
+        assert_eq!(
+            rule.to_string(),
+            r#"ChainRule([StringRule("first" -> "$$second$$"), StringRule("second" -> "world")])"#
+        );
+        assert_eq!(
+            ChainRule::new(vec![])?.convert("Hello world!")?,
+            "Hello world!"
+        );
+        assert_eq!(ChainRule::new(vec![])?.name(), "unknown");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::{Rule, RuleSpec};
+
+/// Apply a rule only to the sections of the template between two markers.
+///
+/// The markers are kept in the output. Every section between a start and an end marker is
+/// converted separately.
+/// ```rust
+/// # use replacer::rule::{Rule, CompositeRule, StringRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = CompositeRule::new(
+///     Box::new(StringRule::new("name", "Point")?),
+///     "// BEGIN",
+///     "// END",
+/// )?;
+/// assert_eq!(
+///     rule.convert("$$name$$\n// BEGIN\n$$name$$\n// END\n$$name$$")?,
+///     "$$name$$\n// BEGIN\nPoint\n// END\n$$name$$"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CompositeRule {
+    /// Rule that will be applied to the sections.
+    inner: Box<dyn Rule>,
+    /// Marker at the start of a section.
+    start_marker: String,
+    /// Marker at the end of a section.
+    end_marker: String,
+}
+
+impl Rule for CompositeRule {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let mut converted = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find(&self.start_marker) {
+            // Copy everything up to and including the start marker
+            let section_start = start + self.start_marker.len();
+            converted.push_str(&rest[..section_start]);
+            rest = &rest[section_start..];
+
+            let end = match rest.find(&self.end_marker) {
+                Some(end) => end,
+                None => bail!(
+                    "section starting with `{}` is missing the end marker `{}`",
+                    self.start_marker,
+                    self.end_marker
+                ),
+            };
+            converted.push_str(&self.inner.convert(&rest[..end])?);
+            converted.push_str(&self.end_marker);
+            rest = &rest[end + self.end_marker.len()..];
+        }
+        converted.push_str(rest);
+
+        Ok(converted)
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Composite {
+            inner: Box::new(self.inner.to_spec()?),
+            start_marker: self.start_marker.clone(),
+            end_marker: self.end_marker.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for CompositeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CompositeRule(")?;
+        self.inner.summary(f)?;
+        write!(
+            f,
+            " between {:?} and {:?})",
+            self.start_marker, self.end_marker
+        )
+    }
+}
+
+impl CompositeRule {
+    /// Setup a new rule.
+    pub fn new(inner: Box<dyn Rule>, start_marker: &str, end_marker: &str) -> Result<Self> {
+        if start_marker.is_empty() || end_marker.is_empty() {
+            bail!("section markers can't be empty");
+        }
+
+        Ok(Self {
+            inner,
+            start_marker: start_marker.to_string(),
+            end_marker: end_marker.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::rule::StringRule;
+
+    #[test]
+    fn composite_rule() -> Result<()> {
+        let rule = CompositeRule::new(
+            Box::new(StringRule::new("replace", "world")?),
+            "/* start */",
+            "/* end */",
+        )?;
+        assert_eq!(
+            rule.convert("$$replace$$ /* start */ $$replace$$ /* end */ $$replace$$ /* start */$$replace$$/* end */")?,
+            "$$replace$$ /* start */ world /* end */ $$replace$$ /* start */world/* end */"
+        );
+        assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello $$replace$$!");
+        assert!(rule.convert("/* start */ $$replace$$").is_err());
+        assert!(
+            CompositeRule::new(Box::new(StringRule::new("replace", "world")?), "", "").is_err()
+        );
+
+        Ok(())
+    }
+}
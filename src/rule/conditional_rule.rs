@@ -0,0 +1,99 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{Rule, RuleSpec};
+
+/// Apply a rule only when another rule changes the template.
+///
+/// When the condition rule changes the template, the other rule is applied to the result of
+/// the condition. Otherwise the template is returned unchanged.
+/// ```rust
+/// # use replacer::rule::{Rule, ConditionalRule, StringRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = ConditionalRule::new(
+///     Box::new(StringRule::new("name", "Point")?),
+///     Box::new(StringRule::new("derive", "#[derive(Debug)]")?),
+/// )?;
+/// assert_eq!(rule.convert("$$derive$$ struct $$name$$;")?, "#[derive(Debug)] struct Point;");
+/// assert_eq!(rule.convert("$$derive$$ struct Point;")?, "$$derive$$ struct Point;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ConditionalRule {
+    /// Rule that needs to change the template.
+    condition: Box<dyn Rule>,
+    /// Rule that will be applied when the condition changed the template.
+    then_rule: Box<dyn Rule>,
+}
+
+impl Rule for ConditionalRule {
+    fn name(&self) -> &str {
+        self.condition.name()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let converted = self.condition.convert(template)?;
+        if converted == template {
+            return Ok(converted);
+        }
+
+        self.then_rule.convert(&converted)
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Conditional {
+            condition: Box::new(self.condition.to_spec()?),
+            then_rule: Box::new(self.then_rule.to_spec()?),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ConditionalRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConditionalRule(")?;
+        self.condition.summary(f)?;
+        write!(f, " => ")?;
+        self.then_rule.summary(f)?;
+        write!(f, ")")
+    }
+}
+
+impl ConditionalRule {
+    /// Setup a new rule.
+    pub fn new(condition: Box<dyn Rule>, then_rule: Box<dyn Rule>) -> Result<Self> {
+        Ok(Self {
+            condition,
+            then_rule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::rule::{StringRule, TypeRule};
+
+    #[test]
+    fn conditional_rule() -> Result<()> {
+        let rule = ConditionalRule::new(
+            Box::new(TypeRule::new("replace", "i32")?),
+            Box::new(StringRule::new("replace", "world")?),
+        )?;
+        assert_eq!(
+            rule.convert("$$replace$$ <replacer::rust_type!(replace; String;)>")?,
+            "world <i32>"
+        );
+        assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello $$replace$$!");
+        assert_eq!(rule.name(), "replace");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust constant with a placeholder constant that can be compiled.
+///
+/// ```rust
+/// // Private
+/// replacer::rust_const!(replace_with_const; PLACEHOLDER: usize = 0;);
+/// // Public
+/// replacer::rust_const!(pub replace_with_other_const; OTHER_PLACEHOLDER: [u8; 2] = [0; 2];);
+/// ```
+#[macro_export]
+macro_rules! rust_const {
+    // Private
+    ($_name:ident; $placeholder:ident: $ty:ty = $value:expr;) => {
+        #[allow(dead_code)]
+        const $placeholder: $ty = $value;
+    };
+    // Public
+    (pub $_name:ident; $placeholder:ident: $ty:ty = $value:expr;) => {
+        #[allow(dead_code)]
+        pub const $placeholder: $ty = $value;
+    };
+}
+
+/// Replace a Rust constant.
+/// ```rust
+/// # use replacer::rule::{Rule, ConstRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = ConstRule::new("max_size", "MAX_SIZE: usize = 1024")?;
+/// assert_eq!(rule.convert("replacer::rust_const! {max_size; PLACEHOLDER: usize = 0;}")?,
+///     "const MAX_SIZE: usize = 1024;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ConstRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for ConstRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}const {};",
+                &caps.name("pub").map_or("", |cap| cap.as_str()),
+                replace_with,
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Const {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ConstRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ConstRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl ConstRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_const!\s*[\({{](?P<pub>pub )?{};{};[\)}}];?",
+            matches,
+            tokens_pattern()
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn const_rule() -> Result<()> {
+        assert_eq!(
+            ConstRule::new("replace", "MAX_SIZE: usize = 1024")?
+                .convert("replacer::rust_const! {replace; PLACEHOLDER: usize = 0;}")?,
+            "const MAX_SIZE: usize = 1024;"
+        );
+        assert_eq!(
+            ConstRule::new("replace", "MAX_SIZE: usize = 1024")?
+                .convert("replacer::rust_const! {pub replace; PLACEHOLDER: usize = 0;}")?,
+            "pub const MAX_SIZE: usize = 1024;"
+        );
+        assert_eq!(
+            ConstRule::new("replace", "MAX_SIZE: usize = 1024")?.convert(
+                "replacer::rust_const!(replace; PLACEHOLDER: Buffer<{ 4 * 4 }, [u8; 4]> = Buffer::new([0; 4]););"
+            )?,
+            "const MAX_SIZE: usize = 1024;"
+        );
+        assert_eq!(
+            ConstRule::new("replace", "MAX_SIZE: usize = 1024")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::nested_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust enum with a placeholder enum that can be compiled.
+///
+/// ```rust
+/// // Private
+/// replacer::rust_enum!(replace_with_enum; Direction { Up, Down };);
+/// // Public
+/// replacer::rust_enum!(pub replace_with_other_enum; Shape { Circle(f32), Square { size: f32 } };);
+/// ```
+#[macro_export]
+macro_rules! rust_enum {
+    // Private
+    ($_name:ident; $placeholder:ident {$($variants:tt)*};) => {
+        enum $placeholder { $($variants)* }
+    };
+    // Public
+    (pub $_name:ident; $placeholder:ident {$($variants:tt)*};) => {
+        pub enum $placeholder { $($variants)* }
+    };
+}
+
+/// Replace a Rust enum.
+/// ```rust
+/// # use replacer::rule::{Rule, EnumRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = EnumRule::new("direction", "Direction { North, South, East, West }")?;
+/// assert_eq!(rule.convert("replacer::rust_enum!(direction; Placeholder { A, B };)")?,
+///     "enum Direction { North, South, East, West }");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EnumRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for EnumRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}enum {}",
+                &caps.name("pub").map_or("", |cap| cap.as_str()),
+                replace_with,
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Enum {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for EnumRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EnumRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl EnumRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_enum!\s*[\({{](?P<pub>pub )?{};[^{{]+{};[\)}}]",
+            matches,
+            // Struct-like variants contain nested braces
+            nested_pattern(r"\{", r"\}", "{}", 2)
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn enum_rule() -> Result<()> {
+        assert_eq!(
+            EnumRule::new("replace", "Direction { North, South }")?
+                .convert("replacer::rust_enum! {replace; Placeholder { A, B };}")?,
+            "enum Direction { North, South }"
+        );
+        assert_eq!(
+            EnumRule::new("replace", "Direction { North, South }")?
+                .convert("replacer::rust_enum!(pub replace; Placeholder { A, B };)")?,
+            "pub enum Direction { North, South }"
+        );
+        assert_eq!(
+            EnumRule::new("replace", "Direction { North, South }")?.convert(
+                "replacer::rust_enum!(replace; Placeholder { Foo(i32, u8), Bar { x: i32 } };)"
+            )?,
+            "enum Direction { North, South }"
+        );
+        assert_eq!(
+            EnumRule::new("replace", "Direction { North, South }")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
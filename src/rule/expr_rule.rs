@@ -1,7 +1,9 @@
+use std::fmt;
+
 use anyhow::Result;
 use regex::Regex;
 
-use crate::Rule;
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
 
 /// Template macro for replacing a Rust expression with a placeholder expression that can be compiled.
 ///
@@ -16,6 +18,9 @@ macro_rules! rust_expr {
 }
 
 /// Replace a Rust expression.
+///
+/// The placeholder expression can contain semicolons inside delimiters, like
+/// `f([0; 4])` or `{ let a = 1; a }`.
 /// ```rust
 /// # use replacer::rule::{Rule, ExprRule};
 /// # fn main() -> anyhow::Result<()> {
@@ -25,7 +30,10 @@ macro_rules! rust_expr {
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct ExprRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
     /// What the keyword will be replaced with.
     replace_with: String,
     /// Regex used to find the macro.
@@ -33,20 +41,54 @@ pub struct ExprRule {
 }
 
 impl Rule for ExprRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
     fn convert(&self, template: &str) -> Result<String> {
         let replace_with: &str = &self.replace_with;
         let replace = self.regex.replace_all(template, replace_with);
 
         Ok(replace.into_owned())
     }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Expr {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ExprRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ExprRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
 }
 
 impl ExprRule {
     /// Setup a new rule.
     pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
-        let regex = Regex::new(&format!(r"replacer::rust_expr!\({};[^;]+;\)", matches))?;
+        let regex = Regex::new(&format!(
+            r"replacer::rust_expr!\s*[\({{\[]{};{};\s*[\)}}\]]",
+            matches,
+            tokens_pattern()
+        ))?;
 
         Ok(Self {
+            matches: matches.to_string(),
             replace_with: replace_with.to_string(),
             regex,
         })
@@ -66,6 +108,22 @@ mod tests {
                 .convert("println!(\"{}\", replacer::rust_expr!(replace; true;));")?,
             "println!(\"{}\", 1 + 1);"
         );
+        assert_eq!(
+            ExprRule::new("replace", "1 + 1")?
+                .convert("let a = replacer::rust_expr! {replace; true;};")?,
+            "let a = 1 + 1;"
+        );
+        assert_eq!(
+            ExprRule::new("replace", "1 + 1")?
+                .convert("let a = replacer::rust_expr![replace; true;];")?,
+            "let a = 1 + 1;"
+        );
+        assert_eq!(
+            ExprRule::new("replace", "1 + 1")?.convert(
+                "let a = replacer::rust_expr!(replace; f(a, [0; 4], { let b = (1, 2); b.0 });) + replacer::rust_expr!(replace; (a, b););"
+            )?,
+            "let a = 1 + 1 + 1 + 1;"
+        );
         assert_eq!(
             ExprRule::new("replace", "1 + 1")?.convert("Hello world!")?,
             "Hello world!"
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use crate::{Rule, RuleSpec};
+
+/// Rule that always fails with a message.
+///
+/// Combined with [`crate::rule::ConditionalRule`] this can be used to guard against
+/// placeholders that must not be present in the template.
+/// ```rust
+/// # use replacer::{rule::{ConditionalRule, FailRule, StringRule}, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .rule(ConditionalRule::new(
+///         Box::new(StringRule::new("required", "")?),
+///         Box::new(FailRule::new("placeholder `required` is not filled in")?),
+///     )?)
+///     .build();
+///
+/// assert_eq!(template.apply("Hello world!")?, "Hello world!");
+/// assert!(template.apply("Hello $$required$$!").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FailRule {
+    /// The message of the error.
+    message: String,
+}
+
+impl Rule for FailRule {
+    fn convert(&self, _template: &str) -> Result<String> {
+        Err(anyhow!("{}", self.message))
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Fail {
+            message: self.message.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for FailRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FailRule({:?})", self.message)
+    }
+}
+
+impl FailRule {
+    /// Setup a new rule.
+    pub fn new(message: &str) -> Result<Self> {
+        Ok(Self {
+            message: message.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn fail_rule() -> Result<()> {
+        let rule = FailRule::new("something went wrong")?;
+        assert_eq!(
+            rule.convert("Hello world!").unwrap_err().to_string(),
+            "something went wrong"
+        );
+        assert!(!rule.matches_any("Hello world!"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{rule::find_macro_invocations, Rule, RuleSpec};
+
+/// Template macro for replacing the body of a Rust function with a placeholder body that can be
+/// compiled.
+///
+/// The macro must be the only thing in the body of the function. A placeholder body like
+/// `{ todo!() }` returns `!`, so it compiles for every return type.
+///
+/// ```rust
+/// fn compute(x: i32) -> i32 {
+///     replacer::rust_fn_body!(replace_with_body; { x + 1 };)
+/// }
+/// # assert_eq!(compute(1), 2);
+///
+/// fn not_yet_implemented() -> String {
+///     replacer::rust_fn_body!(replace_with_other_body; { todo!() };)
+/// }
+/// ```
+#[macro_export]
+macro_rules! rust_fn_body {
+    ($_name:ident; $placeholder:block;) => {
+        $placeholder
+    };
+}
+
+/// Replace the body of a Rust function.
+///
+/// The replacement is the full body of the function including the braces, it replaces both the
+/// macro and the braces of the function surrounding it.
+/// Because bodies can contain any code the macro is found by balancing the delimiters.
+/// ```rust
+/// # use replacer::rule::{Rule, FnBodyRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = FnBodyRule::new("compute_body", "{ let y = x * 2; y + 1 }")?;
+/// assert_eq!(rule.convert("fn compute(x: i32) -> i32 {\n    replacer::rust_fn_body!(compute_body; { todo!() };)\n}")?,
+///     "fn compute(x: i32) -> i32 { let y = x * 2; y + 1 }");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FnBodyRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+}
+
+impl Rule for FnBodyRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let ranges = find_macro_invocations(template, "rust_fn_body", &self.matches)?;
+
+        let mut result = String::with_capacity(template.len());
+        let mut last_end = 0;
+        for range in ranges {
+            // Also replace the braces of the function body surrounding the macro
+            let before = template[last_end..range.start].trim_end();
+            let after = template[range.end..].trim_start();
+            let (start, end) = match (before.strip_suffix('{'), after.strip_prefix('}')) {
+                (Some(before), Some(after)) => {
+                    (last_end + before.len(), template.len() - after.len())
+                }
+                _ => (range.start, range.end),
+            };
+
+            result.push_str(&template[last_end..start]);
+            result.push_str(&self.replace_with);
+            last_end = end;
+        }
+        result.push_str(&template[last_end..]);
+
+        Ok(result)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        // An error means an invocation has been found but it's not balanced
+        find_macro_invocations(template, "rust_fn_body", &self.matches)
+            .map_or(true, |ranges| !ranges.is_empty())
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        // An unbalanced invocation is still counted, applying the rule will fail on it
+        find_macro_invocations(template, "rust_fn_body", &self.matches)
+            .map_or(1, |ranges| ranges.len())
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::FnBody {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for FnBodyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FnBodyRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl FnBodyRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn fn_body_rule() -> Result<()> {
+        assert_eq!(
+            FnBodyRule::new("replace", "{ run(); 2 }")?.convert(
+                "fn a() -> i32 { replacer::rust_fn_body!(replace; { let b = { 1 }; todo!() };) }"
+            )?,
+            "fn a() -> i32 { run(); 2 }"
+        );
+        assert_eq!(
+            FnBodyRule::new("replace", "{ 2 }")?.convert(
+                "fn a() -> i32 { replacer::rust_fn_body!(other; { 1 };) }\nfn b() -> i32 {\n    replacer::rust_fn_body! {replace; { 1 };}\n}"
+            )?,
+            "fn a() -> i32 { replacer::rust_fn_body!(other; { 1 };) }\nfn b() -> i32 { 2 }"
+        );
+        assert_eq!(
+            FnBodyRule::new("replace", "{ 2 }")?.convert(
+                "fn a() -> char { replacer::rust_fn_body!(replace; { '}' };) }\nfn b() {}"
+            )?,
+            "fn a() -> char { 2 }\nfn b() {}"
+        );
+        assert!(FnBodyRule::new("replace", "{ 2 }")?
+            .convert("fn a() { replacer::rust_fn_body!(replace; { 1 };")
+            .is_err());
+        assert_eq!(
+            FnBodyRule::new("replace", "{ 2 }")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust function signature with a placeholder signature that can be compiled.
+///
+/// The signature is emitted without a body, so it can be used for trait methods.
+///
+/// ```rust
+/// trait Shape {
+///     replacer::rust_fn! {replace_with_fn; fn area(&self) -> i32;}
+///     replacer::rust_fn!(replace_with_other_fn; fn name(&self) -> String;);
+/// }
+///
+/// struct Circle;
+///
+/// impl Circle {
+///     replacer::rust_fn! {pub(crate) replace_with_method; fn new() -> Self { Circle }}
+/// }
+/// ```
+#[macro_export]
+macro_rules! rust_fn {
+    ($vis:vis $_name:ident; $($signature:tt)*) => {
+        $vis $($signature)*
+    };
+}
+
+/// Replace a Rust function signature.
+/// ```rust
+/// # use replacer::rule::{Rule, FnRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = FnRule::new("area", "area(&self) -> f64")?;
+/// assert_eq!(rule.convert("replacer::rust_fn! {area; fn placeholder(&self) -> i32;}")?,
+///     "fn area(&self) -> f64;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FnRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for FnRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}fn {};",
+                &caps.name("vis").map_or("", |cap| cap.as_str()),
+                replace_with,
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Fn {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for FnRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FnRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl FnRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_fn!\s*[\({{](?P<vis>pub(?:\s*\([^)]*\))?\s+)?{};\s*fn\s{};[\)}}];?",
+            matches,
+            tokens_pattern()
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn fn_rule() -> Result<()> {
+        assert_eq!(
+            FnRule::new("replace", "add(a: i32, b: i32) -> i32")?
+                .convert("replacer::rust_fn! {replace; fn placeholder();}")?,
+            "fn add(a: i32, b: i32) -> i32;"
+        );
+        assert_eq!(
+            FnRule::new("replace", "add(a: i32, b: i32) -> i32")?
+                .convert("replacer::rust_fn! {pub replace; fn placeholder();}")?,
+            "pub fn add(a: i32, b: i32) -> i32;"
+        );
+        assert_eq!(
+            FnRule::new("replace", "add(a: i32, b: i32) -> i32")?
+                .convert("replacer::rust_fn! {pub(crate) replace; fn placeholder();}")?,
+            "pub(crate) fn add(a: i32, b: i32) -> i32;"
+        );
+        assert_eq!(
+            FnRule::new("replace", "area(&self) -> f64")?.convert(
+                "trait Shape {\n    replacer::rust_fn!(replace; fn placeholder(&self););\n}"
+            )?,
+            "trait Shape {\n    fn area(&self) -> f64;\n}"
+        );
+        assert_eq!(
+            FnRule::new("replace", "first(self) -> u8")?.convert(
+                "replacer::rust_fn!{replace; fn placeholder<const N: usize>(a: [u8; { N }], b: (u8, (u8, u8))) -> [u8; 4] where Self: Sized;}"
+            )?,
+            "fn first(self) -> u8;"
+        );
+        assert_eq!(
+            FnRule::new("replace", "add()")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{rule::find_macro_invocations, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust impl block with a placeholder impl block that can be compiled.
+///
+/// ```rust
+/// struct Point;
+///
+/// replacer::rust_impl! {replace_with_impl; impl Point {}}
+/// ```
+#[macro_export]
+macro_rules! rust_impl {
+    ($_name:ident; $($placeholder:tt)*) => {
+        $($placeholder)*
+    };
+}
+
+/// Replace a Rust impl block.
+///
+/// The replacement is inserted verbatim, so it should contain the full impl block.
+/// Because impl blocks can contain any code the macro is found by balancing the delimiters.
+/// ```rust
+/// # use replacer::rule::{Rule, ImplRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = ImplRule::new("point", "impl Point { fn new() -> Self { Self } }")?;
+/// assert_eq!(rule.convert("replacer::rust_impl! {point; impl Placeholder {}}")?,
+///     "impl Point { fn new() -> Self { Self } }");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ImplRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+}
+
+impl Rule for ImplRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let ranges = find_macro_invocations(template, "rust_impl", &self.matches)?;
+
+        let mut result = String::with_capacity(template.len());
+        let mut last_end = 0;
+        for range in ranges {
+            result.push_str(&template[last_end..range.start]);
+            result.push_str(&self.replace_with);
+            // The semicolon after a parenthesized invocation isn't valid after an impl block
+            last_end = range.end + usize::from(template[range.end..].starts_with(';'));
+        }
+        result.push_str(&template[last_end..]);
+
+        Ok(result)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        // An error means an invocation has been found but it's not balanced
+        find_macro_invocations(template, "rust_impl", &self.matches)
+            .map_or(true, |ranges| !ranges.is_empty())
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        // An unbalanced invocation is still counted, applying the rule will fail on it
+        find_macro_invocations(template, "rust_impl", &self.matches)
+            .map_or(1, |ranges| ranges.len())
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Impl {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ImplRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ImplRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl ImplRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn impl_rule() -> Result<()> {
+        assert_eq!(
+            ImplRule::new("replace", "impl Point {}")?.convert(
+                "replacer::rust_impl! {replace; impl Placeholder { fn a() { let b = { 1 }; } }}"
+            )?,
+            "impl Point {}"
+        );
+        assert_eq!(
+            ImplRule::new("replace", "impl Point {}")?.convert(
+                "replacer::rust_impl! {other; impl Other {}}\nreplacer::rust_impl!(replace; impl Placeholder {});"
+            )?,
+            "replacer::rust_impl! {other; impl Other {}}\nimpl Point {}"
+        );
+        assert_eq!(
+            ImplRule::new("replace", "impl P {}")?.convert(
+                "replacer::rust_impl! {replace; impl Placeholder { fn a() -> char { '}' } fn b() -> &'static str { \"}\" } /* } */ }} // rest"
+            )?,
+            "impl P {} // rest"
+        );
+        assert!(ImplRule::new("replace", "impl Point {}")?
+            .convert("replacer::rust_impl! {replace; impl Placeholder {}")
+            .is_err());
+        assert_eq!(
+            ImplRule::new("replace", "impl Point {}")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    fmt,
+    io::Write,
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{Rule, RuleSpec};
+
+/// Create a rule from its description the first time it's used.
+///
+/// The regexes of the built-in rules are compiled when the rule is created, describing the
+/// rule with a [`RuleSpec`] instead delays that until the rule is needed. Clones of the rule
+/// share the created rule.
+/// ```rust
+/// # use replacer::{rule::{LazyRule, Rule}, RuleSpec};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = LazyRule::new(RuleSpec::Type {
+///     matches: "replace".to_string(),
+///     replace_with: "i32".to_string(),
+/// });
+/// assert!(!rule.is_compiled());
+///
+/// assert_eq!(rule.convert("let a = <replacer::rust_type!(replace; String;)>::new();")?,
+///     "let a = <i32>::new();");
+/// assert!(rule.is_compiled());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LazyRule {
+    /// Description of the rule.
+    spec: RuleSpec,
+    /// The rule, or the error message when it couldn't be created.
+    rule: Arc<OnceLock<Result<Box<dyn Rule>, String>>>,
+}
+
+impl Rule for LazyRule {
+    fn name(&self) -> &str {
+        // Taken from the description, so the rule isn't created for this
+        self.spec.name()
+    }
+
+    fn rule_type(&self) -> &'static str {
+        self.spec.rule_type()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        self.rule()?.convert(template)
+    }
+
+    fn convert_to_writer(&self, template: &str, output: &mut dyn Write) -> Result<()> {
+        self.rule()?.convert_to_writer(template, output)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.rule().is_ok_and(|rule| rule.matches_any(template))
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.rule().map_or(0, |rule| rule.count_matches(template))
+    }
+
+    fn is_nop(&self) -> bool {
+        // Checked before the rule is used, so don't create it for this
+        self.spec == RuleSpec::Nop
+    }
+
+    fn is_suspicious(&self) -> bool {
+        self.rule().is_ok_and(|rule| rule.is_suspicious())
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(self.spec.clone())
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for LazyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rule.get() {
+            Some(Ok(rule)) => rule.summary(f),
+            _ => write!(f, "LazyRule({:?})", self.spec),
+        }
+    }
+}
+
+impl LazyRule {
+    /// Setup a new rule, it's created from the description when it's used for the first time.
+    pub fn new(spec: RuleSpec) -> Self {
+        Self {
+            spec,
+            rule: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Whether the rule has already been created from the description.
+    pub fn is_compiled(&self) -> bool {
+        self.rule.get().is_some()
+    }
+
+    /// Get the rule, creating it when it doesn't exist yet.
+    fn rule(&self) -> Result<&dyn Rule> {
+        match self.rule.get_or_init(|| {
+            self.spec
+                .clone()
+                .into_rule()
+                .map_err(|err| format!("{:#}", err))
+        }) {
+            Ok(rule) => Ok(rule.as_ref()),
+            Err(err) => Err(anyhow!("{}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn lazy_rule() -> Result<()> {
+        let rule = LazyRule::new(RuleSpec::String {
+            matches: "replace".to_string(),
+            replace_with: "world".to_string(),
+            case_insensitive: false,
+            whole_word: false,
+            count: None,
+        });
+        let clone = rule.clone();
+        assert!(!rule.is_compiled());
+        assert_eq!(rule.to_string(), format!("LazyRule({:?})", rule.spec));
+        assert!(rule.to_spec().is_some());
+        assert_eq!(rule.name(), "replace");
+        assert_eq!(
+            rule.rule_type(),
+            std::any::type_name::<crate::rule::StringRule>()
+        );
+        assert!(!rule.is_compiled());
+
+        assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello world!");
+        assert!(rule.is_compiled());
+        assert!(clone.is_compiled());
+        assert_eq!(rule.name(), "replace");
+        assert_eq!(rule.to_string(), r#"StringRule("replace" -> "world")"#);
+
+        let rule = LazyRule::new(RuleSpec::String {
+            matches: "not valid".to_string(),
+            replace_with: "world".to_string(),
+            case_insensitive: false,
+            whole_word: false,
+            count: None,
+        });
+        assert!(rule.convert("Hello $$replace$$!").is_err());
+        assert!(rule.convert("Hello $$replace$$!").is_err());
+        assert_eq!(rule.name(), "not valid");
+        assert!(!rule.matches_any("Hello $$replace$$!"));
+
+        assert!(LazyRule::new(RuleSpec::Nop).is_nop());
+
+        Ok(())
+    }
+}
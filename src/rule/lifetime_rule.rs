@@ -0,0 +1,129 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{NoExpand, Regex};
+
+use crate::{Rule, RuleSpec};
+
+/// Template macro for replacing a Rust lifetime with a placeholder lifetime.
+///
+/// Rust doesn't allow macros in the position of a lifetime, so unlike the other template
+/// macros a template using this can't be compiled before the rules are applied.
+///
+/// ```rust,compile_fail
+/// struct Wrapper<replacer::rust_lifetime!(replace_with_lifetime; 'a;)> {
+///     inner: &'a str,
+/// }
+/// ```
+#[macro_export]
+macro_rules! rust_lifetime {
+    ($_name:ident; $placeholder:lifetime;) => {
+        $placeholder
+    };
+}
+
+/// Replace a Rust lifetime.
+/// ```rust
+/// # use replacer::rule::{Rule, LifetimeRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = LifetimeRule::new("lifetime", "'static")?;
+/// assert_eq!(rule.convert("&replacer::rust_lifetime!(lifetime; 'a;) str")?, "&'static str");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LifetimeRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for LifetimeRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self
+            .regex
+            .replace_all(template, NoExpand(&self.replace_with));
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Lifetime {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for LifetimeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LifetimeRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl LifetimeRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_lifetime!\s*[\({{]{};\s*'\w+\s*;[\)}}]",
+            matches
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn lifetime_rule() -> Result<()> {
+        assert_eq!(
+            LifetimeRule::new("replace", "'b")?.convert(
+                "struct Point<replacer::rust_lifetime!(replace; 'a;), T> { x: &replacer::rust_lifetime!(replace; 'a;) T }"
+            )?,
+            "struct Point<'b, T> { x: &'b T }"
+        );
+        assert_eq!(
+            LifetimeRule::new("replace", "'b")?
+                .convert("impl<replacer::rust_lifetime! {replace; 'a;}> Point {}")?,
+            "impl<'b> Point {}"
+        );
+        assert_eq!(
+            LifetimeRule::new("replace", "'b")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::indent, Rule, RuleSpec};
+
+/// Replace whole lines marked with a `// REPLACE:name` comment.
+///
+/// The line containing the comment, including its line ending, is replaced. Every line of the
+/// replacement gets the indentation of the comment.
+/// ```rust
+/// # use replacer::rule::{Rule, LineRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = LineRule::new("init", "let x = 1;\nlet y = 2;")?;
+/// assert_eq!(
+///     rule.convert("fn main() {\n    // REPLACE:init\n    println!(\"{}\", x + y);\n}")?,
+///     "fn main() {\n    let x = 1;\n    let y = 2;\n    println!(\"{}\", x + y);\n}"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LineRule {
+    /// The name after `// REPLACE:` that will be matched with.
+    matches: String,
+    /// What the line will be replaced with.
+    replace_with: String,
+    /// Regex used to find the line.
+    regex: Regex,
+}
+
+impl Rule for LineRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}{}",
+                indent(&self.replace_with, &caps["indentation"]),
+                &caps["newline"]
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Line {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for LineRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LineRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl LineRule {
+    /// Setup a new rule.
+    ///
+    /// Lines starting with `// REPLACE:name` are matched, text after the name is ignored.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"(?m)^(?P<indentation>[ \t]*)//\s*REPLACE:{}\b[^\n]*?(?P<newline>\r?\n|\z)",
+            regex::escape(matches)
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn line_rule() -> Result<()> {
+        assert_eq!(
+            LineRule::new("replace", "a();\n\nb();")?.convert(
+                "fn main() {\r\n\t// REPLACE:replace with the calls\r\n\t// REPLACE:replace_other\r\n}\n// REPLACE:replace"
+            )?,
+            "fn main() {\r\n\ta();\n\n\tb();\r\n\t// REPLACE:replace_other\r\n}\na();\n\nb();"
+        );
+        assert_eq!(
+            LineRule::new("replace", "a();")?.convert("let a = 1; // REPLACE:replace\n")?,
+            "let a = 1; // REPLACE:replace\n"
+        );
+        assert_eq!(
+            LineRule::new("replace", "a();")?
+                .count_matches("// REPLACE:replace\n// REPLACE:replace"),
+            2
+        );
+        assert_eq!(
+            LineRule::new("replace", "a();")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::{Rule, RuleSpec};
+
+/// Replace a literal string.
+///
+/// Unlike [`crate::rule::StringRule`] there's no `$$..$$` wrapping, the string is matched as is.
+/// This is useful for templates that don't contain placeholders, like replacing a function name
+/// or a crate path.
+/// ```rust
+/// # use replacer::rule::{Rule, LiteralRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = LiteralRule::new("old_crate::", "new_crate::")?;
+/// assert_eq!(rule.convert("use old_crate::Point;")?, "use new_crate::Point;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LiteralRule {
+    /// The string that will be matched with.
+    from: String,
+    /// What the string will be replaced with.
+    to: String,
+}
+
+impl Rule for LiteralRule {
+    fn name(&self) -> &str {
+        &self.from
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        Ok(template.replace(&self.from, &self.to))
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        template.contains(&self.from)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        template.matches(&self.from).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Literal {
+            from: self.from.clone(),
+            to: self.to.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for LiteralRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LiteralRule({:?} -> {:?})", self.from, self.to)
+    }
+}
+
+impl LiteralRule {
+    /// Setup a new rule.
+    pub fn new(from: &str, to: &str) -> Result<Self> {
+        if from.is_empty() {
+            // An empty string would match between every character
+            bail!("the string to replace can't be empty");
+        }
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn literal_rule() -> Result<()> {
+        assert_eq!(
+            LiteralRule::new("old_fn", "new_fn")?.convert("old_fn(); old_fn(); $$old_fn$$")?,
+            "new_fn(); new_fn(); $$new_fn$$"
+        );
+        assert_eq!(
+            LiteralRule::new("$1.*", "x")?.convert("let a = \"$1.*\";")?,
+            "let a = \"x\";"
+        );
+        assert_eq!(
+            LiteralRule::new("old_fn", "new_fn")?.count_matches("old_fn(); old_fn();"),
+            2
+        );
+        assert!(LiteralRule::new("", "new_fn").is_err());
+        assert_eq!(
+            LiteralRule::new("old_fn", "new_fn")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
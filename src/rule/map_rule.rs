@@ -0,0 +1,147 @@
+use std::{collections::HashMap, fmt};
+
+use anyhow::{bail, Result};
+use regex::{Captures, Regex};
+
+use crate::{rule::validate_identifier, Rule, RuleSpec};
+
+/// Replace multiple keywords in a single pass.
+///
+/// Like [`crate::rule::StringRule`] the keywords are wrapped in `$$..$$`. Because all keywords
+/// are replaced at once, a replacement containing another keyword is never replaced again.
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use replacer::rule::{Rule, MapRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let mut map = HashMap::new();
+/// map.insert("greeting".to_string(), "Hello $$name$$".to_string());
+/// map.insert("name".to_string(), "world".to_string());
+///
+/// let rule = MapRule::new(map)?;
+/// assert_eq!(rule.convert("$$greeting$$, $$name$$!")?, "Hello $$name$$, world!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MapRule {
+    /// The keywords and what they will be replaced with.
+    map: HashMap<String, String>,
+    /// Regex matching all keywords.
+    regex: Regex,
+}
+
+impl Rule for MapRule {
+    fn name(&self) -> &str {
+        // The map is never empty, so the first keyword can be used
+        self.map.keys().min().map_or("unknown", String::as_str)
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self
+            .regex
+            .replace_all(template, |caps: &Captures| self.map[&caps["name"]].clone());
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Map {
+            map: self.map.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for MapRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries = self
+            .map
+            .iter()
+            .map(|(matches, replace_with)| format!("{:?} -> {:?}", matches, replace_with))
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        write!(f, "MapRule([{}])", entries.join(", "))
+    }
+}
+
+impl MapRule {
+    /// Setup a new rule.
+    ///
+    /// Every keyword must be a valid Rust identifier.
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use replacer::rule::MapRule;
+    /// assert!(MapRule::new(HashMap::new()).is_err());
+    /// ```
+    pub fn new(map: HashMap<String, String>) -> Result<Self> {
+        if map.is_empty() {
+            bail!("map rule doesn't contain any keywords");
+        }
+
+        let mut keywords = map.keys().collect::<Vec<_>>();
+        keywords.sort();
+        for keyword in &keywords {
+            validate_identifier(keyword)?;
+        }
+
+        let regex = Regex::new(&format!(
+            r"\$\$(?P<name>{})\$\$",
+            keywords
+                .iter()
+                .map(|keyword| regex::escape(keyword))
+                .collect::<Vec<_>>()
+                .join("|")
+        ))?;
+
+        Ok(Self { map, regex })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn map_rule() -> Result<()> {
+        let map = vec![
+            ("a".to_string(), "$$ab$$".to_string()),
+            ("ab".to_string(), "$$a$$".to_string()),
+        ]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let rule = MapRule::new(map)?;
+
+        assert_eq!(
+            rule.convert("$$a$$ $$ab$$ $$abc$$")?,
+            "$$ab$$ $$a$$ $$abc$$"
+        );
+        assert_eq!(rule.count_matches("$$a$$ $$ab$$ $$abc$$"), 2);
+        assert_eq!(rule.name(), "a");
+        assert_eq!(rule.convert("Hello world!")?, "Hello world!");
+        assert_eq!(
+            rule.to_string(),
+            r#"MapRule(["a" -> "$$ab$$", "ab" -> "$$a$$"])"#
+        );
+
+        let map = vec![("a b".to_string(), "c".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        assert!(MapRule::new(map).is_err());
+
+        Ok(())
+    }
+}
@@ -1,19 +1,491 @@
+pub mod attr_rule;
+pub mod block_rule;
+pub mod chain_rule;
+pub mod composite_rule;
+pub mod conditional_rule;
+pub mod const_rule;
+pub mod enum_rule;
 pub mod expr_rule;
+pub mod fail_rule;
+pub mod fn_body_rule;
+pub mod fn_rule;
+pub mod impl_rule;
+pub mod lazy_rule;
+pub mod lifetime_rule;
+pub mod line_rule;
+pub mod literal_rule;
+pub mod map_rule;
+pub mod mod_rule;
+pub mod nop_rule;
+pub mod path_rule;
+pub mod prefix_rule;
+pub mod regex_rule;
+pub mod section_rule;
+pub mod static_rule;
 pub mod string_rule;
 pub mod struct_rule;
+pub mod trait_rule;
 pub mod type_rule;
+pub mod use_rule;
+pub mod version_rule;
+pub mod where_rule;
 
+pub use attr_rule::*;
+pub use block_rule::*;
+pub use chain_rule::*;
+pub use composite_rule::*;
+pub use conditional_rule::*;
+pub use const_rule::*;
+pub use enum_rule::*;
 pub use expr_rule::*;
+pub use fail_rule::*;
+pub use fn_body_rule::*;
+pub use fn_rule::*;
+pub use impl_rule::*;
+pub use lazy_rule::*;
+pub use lifetime_rule::*;
+pub use line_rule::*;
+pub use literal_rule::*;
+pub use map_rule::*;
+pub use mod_rule::*;
+pub use nop_rule::*;
+pub use path_rule::*;
+pub use prefix_rule::*;
+pub use regex_rule::*;
+pub use section_rule::*;
+pub use static_rule::*;
 pub use string_rule::*;
 pub use struct_rule::*;
+pub use trait_rule::*;
 pub use type_rule::*;
+pub use use_rule::*;
+pub use version_rule::*;
+pub use where_rule::*;
 
-use anyhow::Result;
+use std::{fmt, io::Write, ops::Range};
+
+use anyhow::{bail, Result};
 
 /// Generic way to add rules for a single file.
 ///
 /// This trait can be implemented on a struct or enum for custom template handling.
-pub trait Rule {
+/// Rules must be thread-safe so a template can be applied from another thread.
+/// Rules must also implement [`Clone`] so a template can be cloned.
+pub trait Rule: CloneRule + Send + Sync {
     /// Convert the matched values to a string.
     fn convert(&self, template: &str) -> Result<String>;
+
+    /// Convert the matched values and write the result to the output.
+    ///
+    /// This is used for the last rule of a [`crate::Template`] so the result doesn't need to be
+    /// collected in a string first. By default the result of [`Rule::convert`] is written, a
+    /// [`StringRule`] writes the parts of the result directly.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new("replace", "world")?;
+    ///
+    /// let mut output = vec![];
+    /// rule.convert_to_writer("Hello $$replace$$!", &mut output)?;
+    /// assert_eq!(output, b"Hello world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn convert_to_writer(&self, template: &str, output: &mut dyn Write) -> Result<()> {
+        output.write_all(self.convert(template)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// The name of the rule, used in diagnostics.
+    ///
+    /// The built-in rules return the name of the placeholder they match with.
+    ///
+    /// ```rust
+    /// # use replacer::{rule::Rule, TemplateBuilder};
+    /// #[derive(Clone)]
+    /// struct FailingRule;
+    ///
+    /// impl Rule for FailingRule {
+    ///     fn convert(&self, _template: &str) -> anyhow::Result<String> {
+    ///         anyhow::bail!("something went wrong")
+    ///     }
+    ///
+    ///     fn name(&self) -> &str {
+    ///         "failing"
+    ///     }
+    /// }
+    ///
+    /// let template = TemplateBuilder::new().rule(FailingRule).build();
+    /// assert_eq!(
+    ///     format!("{:#}", template.apply("").unwrap_err()),
+    ///     "rule 'failing' failed: something went wrong"
+    /// );
+    /// ```
+    fn name(&self) -> &str {
+        "unknown"
+    }
+
+    /// The type of the rule, used in diagnostics.
+    ///
+    /// By default this is the full type name of the rule.
+    fn rule_type(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Whether applying the rule would change the template.
+    ///
+    /// By default this converts the template and compares the result, a rule returning an
+    /// error is seen as not matching. The built-in rules only search for the placeholder.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new("replace", "world")?;
+    /// assert!(rule.matches_any("Hello $$replace$$!"));
+    /// assert!(!rule.matches_any("Hello world!"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn matches_any(&self, template: &str) -> bool {
+        self.convert(template)
+            .is_ok_and(|converted| converted != template)
+    }
+
+    /// How many places in the template would be replaced by applying the rule.
+    ///
+    /// By default this is `1` when [`Rule::matches_any`] returns `true` and `0` otherwise.
+    /// The built-in rules count every placeholder they match with.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new("replace", "world")?;
+    /// assert_eq!(rule.count_matches("Hello $$replace$$ and $$replace:upper$$!"), 2);
+    /// assert_eq!(rule.count_matches("Hello world!"), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn count_matches(&self, template: &str) -> usize {
+        usize::from(self.matches_any(template))
+    }
+
+    /// Whether the rule never changes the template.
+    ///
+    /// Rules returning `true` are skipped when applying a [`crate::Template`].
+    fn is_nop(&self) -> bool {
+        false
+    }
+
+    /// Whether the rule is probably a mistake, a warning is logged when it's applied.
+    ///
+    /// A [`StringRule`] replacing the placeholder with an empty string is suspicious.
+    fn is_suspicious(&self) -> bool {
+        false
+    }
+
+    /// Describe the rule so it can be serialized, see [`crate::SerializableTemplate`].
+    ///
+    /// By default rules can't be serialized and this returns `None`.
+    fn to_spec(&self) -> Option<crate::RuleSpec> {
+        None
+    }
+
+    /// Write a human-readable summary of the rule, used when displaying or debugging a
+    /// [`crate::Template`].
+    ///
+    /// By default this is the type and the name of the rule, the built-in rules also show what
+    /// the placeholder will be replaced with.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule: Box<dyn Rule> = Box::new(StringRule::new("foo", "bar")?);
+    /// assert_eq!(rule.to_string(), r#"StringRule("foo" -> "bar")"#);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({:?})",
+            crate::short_type_name(self.rule_type()),
+            self.name()
+        )
+    }
+}
+
+impl fmt::Display for dyn Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary(f)
+    }
+}
+
+impl fmt::Debug for dyn Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary(f)
+    }
+}
+
+/// Clone a boxed rule.
+///
+/// This is implemented automatically for every rule that implements [`Clone`].
+pub trait CloneRule {
+    /// Clone the rule into a new box.
+    fn clone_box(&self) -> Box<dyn Rule>;
+}
+
+impl<R> CloneRule for R
+where
+    R: Rule + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn Rule> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Rule> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Create a regex pattern matching balanced delimiters up to a maximum nesting depth.
+pub(crate) fn nested_pattern(open: &str, close: &str, delimiters: &str, depth: usize) -> String {
+    (0..depth).fold(
+        format!("{}[^{}]*{}", open, delimiters, close),
+        |inner, _| format!("{}(?:[^{}]|{})*{}", open, delimiters, inner, close),
+    )
+}
+
+/// Create a regex pattern matching everything up to the next semicolon that isn't nested.
+///
+/// Semicolons and braces can appear inside delimiters, like `[u8; 4]` or `{ N }`.
+pub(crate) fn tokens_pattern() -> String {
+    format!(
+        r"(?:[^;(){{}}\[\]]|{}|{}|{})*",
+        nested_pattern(r"\(", r"\)", "()", 3),
+        nested_pattern(r"\[", r"\]", r"\[\]", 3),
+        nested_pattern(r"\{", r"\}", "{}", 3)
+    )
+}
+
+/// Find the byte ranges of all invocations of a template macro with balanced delimiters.
+///
+/// This is used for macros that can contain arbitrary code, which can't be matched reliably
+/// with a regex. Only invocations where the first argument is `matches` are returned.
+pub(crate) fn find_macro_invocations(
+    template: &str,
+    macro_name: &str,
+    matches: &str,
+) -> Result<Vec<Range<usize>>> {
+    let prefix = format!("replacer::{}!", macro_name);
+    let bytes = template.as_bytes();
+    let skipped = non_code(template);
+
+    let mut ranges = vec![];
+    let mut offset = 0;
+    while let Some(found) = template[offset..].find(&prefix) {
+        let start = offset + found;
+        let after_prefix = start + prefix.len();
+        offset = after_prefix;
+
+        // Skip the whitespace between the macro name and the opening delimiter
+        let body = template[after_prefix..].trim_start();
+        let open = after_prefix + template[after_prefix..].len() - body.len();
+        if !body.starts_with(['(', '{', '[']) {
+            continue;
+        }
+
+        // Check whether the first argument is the name we're looking for
+        let arguments = body[1..].trim_start();
+        let is_match = arguments
+            .strip_prefix(matches)
+            .is_some_and(|rest| rest.trim_start().starts_with(';'));
+        if !is_match {
+            continue;
+        }
+
+        let mut depth = 0usize;
+        let mut end = None;
+        let mut index = open;
+        while index < bytes.len() {
+            // Delimiters inside literals and comments don't count
+            if let Ok(skip) = skipped.binary_search_by_key(&index, |range| range.start) {
+                index = skipped[skip].end;
+                continue;
+            }
+
+            match bytes[index] {
+                b'(' | b'{' | b'[' => depth += 1,
+                b')' | b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(index + 1);
+                        break;
+                    }
+                }
+                _ => (),
+            }
+            index += 1;
+        }
+
+        match end {
+            Some(end) => {
+                ranges.push(start..end);
+                offset = end;
+            }
+            None => bail!(
+                "unbalanced delimiters in `{}` invocation at byte {}",
+                prefix,
+                start
+            ),
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// Find the byte ranges of all string literals in Rust code, including the quotes.
+///
+/// Raw strings are supported, quotes inside comments and character literals are ignored.
+pub(crate) fn string_literals(code: &str) -> Vec<Range<usize>> {
+    literals_and_comments(code).0
+}
+
+/// Find the byte ranges of all string literals, character literals and comments in Rust code.
+///
+/// The ranges are sorted and don't overlap.
+pub(crate) fn non_code(code: &str) -> Vec<Range<usize>> {
+    let (strings, characters, comments) = literals_and_comments(code);
+
+    let mut ranges = [strings, characters, comments].concat();
+    ranges.sort_by_key(|range| range.start);
+
+    ranges
+}
+
+/// Find the byte ranges of all line and block comments in Rust code, including the markers.
+///
+/// Comment markers inside string literals are ignored.
+pub(crate) fn comments(code: &str) -> Vec<Range<usize>> {
+    literals_and_comments(code).2
+}
+
+/// Find the byte ranges of all string literals, character literals and comments in Rust code.
+#[allow(clippy::type_complexity)]
+fn literals_and_comments(code: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>, Vec<Range<usize>>) {
+    let bytes = code.as_bytes();
+
+    let mut ranges = vec![];
+    let mut characters = vec![];
+    let mut comments = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            // Line comment
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                let end = code[index..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| index + end);
+                comments.push(index..end);
+                index = end;
+            }
+            // Block comment
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                let end = code[index + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| index + 2 + end + 2);
+                comments.push(index..end);
+                index = end;
+            }
+            // Character literal, a lifetime is skipped like any other character
+            b'\'' => {
+                let length = match code[index + 1..].chars().next() {
+                    Some('\\') => code[index + 2..]
+                        .find('\'')
+                        .map_or(bytes.len() - index, |end| end + 3),
+                    Some(character)
+                        if bytes.get(index + 1 + character.len_utf8()) == Some(&b'\'') =>
+                    {
+                        character.len_utf8() + 2
+                    }
+                    _ => 1,
+                };
+                if length > 1 {
+                    characters.push(index..index + length);
+                }
+                index += length;
+            }
+            // Raw string literal
+            b'r' if (index == 0 || !is_identifier_byte(bytes[index - 1]))
+                && matches!(bytes.get(index + 1), Some(b'"') | Some(b'#')) =>
+            {
+                let hashes = bytes[index + 1..]
+                    .iter()
+                    .take_while(|byte| **byte == b'#')
+                    .count();
+                if bytes.get(index + 1 + hashes) != Some(&b'"') {
+                    index += 1;
+                    continue;
+                }
+
+                let closing = format!("\"{}", "#".repeat(hashes));
+                let content = index + 2 + hashes;
+                let end = code[content..]
+                    .find(&closing)
+                    .map_or(bytes.len(), |end| content + end + closing.len());
+                ranges.push(index..end);
+                index = end;
+            }
+            // Normal string literal
+            b'"' => {
+                let mut end = index + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    // Skip the escaped character
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let end = (end + 1).min(bytes.len());
+                ranges.push(index..end);
+                index = end;
+            }
+            _ => index += 1,
+        }
+    }
+
+    (ranges, characters, comments)
+}
+
+/// Prefix every non-empty line of the text with the indentation.
+pub(crate) fn indent(text: &str, indentation: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{}", indentation, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Check whether the keyword is a valid Rust identifier.
+pub(crate) fn validate_identifier(matches: &str) -> Result<()> {
+    let mut chars = matches.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|next| next.is_alphanumeric() || next == '_');
+    if !valid {
+        bail!("`{}` is not a valid identifier", matches);
+    }
+
+    Ok(())
+}
+
+/// Whether the byte can be part of an identifier.
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
 }
@@ -0,0 +1,152 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::nested_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust module declaration with a placeholder module that can be compiled.
+///
+/// ```rust
+/// // Declaration, compiles to an empty inline module
+/// replacer::rust_mod!(replace_with_mod; placeholder_mod;);
+/// // Inline, with a visibility
+/// replacer::rust_mod!(pub(crate) replace_with_inline_mod; placeholder_inline_mod { pub fn f() {} };);
+/// ```
+#[macro_export]
+macro_rules! rust_mod {
+    // Declaration
+    ($vis:vis $_name:ident; $placeholder:ident;) => {
+        $vis mod $placeholder {}
+    };
+    // Inline
+    ($vis:vis $_name:ident; $placeholder:ident {$($body:tt)*};) => {
+        $vis mod $placeholder { $($body)* }
+    };
+}
+
+/// Replace the name of a Rust module.
+///
+/// A declaration will be replaced with `mod name;`, an inline module keeps its body.
+/// ```rust
+/// # use replacer::rule::{Rule, ModRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = ModRule::new("module_name", "storage")?;
+/// assert_eq!(rule.convert("replacer::rust_mod! {pub module_name; placeholder_mod;}")?,
+///     "pub mod storage;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ModRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for ModRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            let vis = caps.name("vis").map_or("", |cap| cap.as_str());
+
+            match caps.name("body") {
+                Some(body) => format!("{}mod {} {}", vis, replace_with, body.as_str()),
+                None => format!("{}mod {};", vis, replace_with),
+            }
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Mod {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ModRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ModRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl ModRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_mod!\s*[\({{](?P<vis>pub(?:\s*\([^)]*\))?\s+)?{};\s*\w+\s*(?:(?P<body>{})\s*)?;[\)}}];?",
+            matches,
+            nested_pattern(r"\{", r"\}", "{}", 4)
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn mod_rule() -> Result<()> {
+        assert_eq!(
+            ModRule::new("replace", "storage")?
+                .convert("replacer::rust_mod! {replace; placeholder;}")?,
+            "mod storage;"
+        );
+        assert_eq!(
+            ModRule::new("replace", "storage")?
+                .convert("replacer::rust_mod! {pub(crate) replace; placeholder;}")?,
+            "pub(crate) mod storage;"
+        );
+        assert_eq!(
+            ModRule::new("replace", "storage")?
+                .convert("replacer::rust_mod! {pub replace; placeholder { fn f() { {} } };}")?,
+            "pub mod storage { fn f() { {} } }"
+        );
+        assert_eq!(
+            ModRule::new("replace", "storage")?
+                .convert("replacer::rust_mod!(replace; placeholder;);\nfn main() {}")?,
+            "mod storage;\nfn main() {}"
+        );
+        assert_eq!(
+            ModRule::new("replace", "storage")?
+                .convert("replacer::rust_mod!(replace; placeholder { fn f() {} };);")?,
+            "mod storage { fn f() {} }"
+        );
+        assert_eq!(
+            ModRule::new("replace", "storage")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
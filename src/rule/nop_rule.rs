@@ -0,0 +1,71 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::{Rule, RuleSpec};
+
+/// Rule that doesn't change the template.
+///
+/// This can be used as a placeholder when composing rules.
+/// ```rust
+/// # use replacer::rule::{Rule, NopRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = NopRule::new()?;
+/// assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello $$replace$$!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct NopRule;
+
+impl Rule for NopRule {
+    fn convert(&self, template: &str) -> Result<String> {
+        Ok(template.to_string())
+    }
+
+    fn matches_any(&self, _template: &str) -> bool {
+        false
+    }
+
+    fn is_nop(&self) -> bool {
+        true
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Nop)
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for NopRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NopRule")
+    }
+}
+
+impl NopRule {
+    /// Setup a new rule.
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn nop_rule() -> Result<()> {
+        let rule = NopRule::new()?;
+        assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello $$replace$$!");
+        assert!(!rule.matches_any("Hello $$replace$$!"));
+        assert!(rule.is_nop());
+
+        Ok(())
+    }
+}
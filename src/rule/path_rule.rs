@@ -0,0 +1,117 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{rule::StringRule, Rule, RuleSpec};
+
+/// Replace a keyword with a filesystem path.
+///
+/// The keyword is matched like [`StringRule`]. The path is validated and normalized when the
+/// rule is created: repeated separators, `.` components and trailing separators are removed.
+/// ```rust
+/// # use replacer::rule::{Rule, PathRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = PathRule::new("output_dir", "/some//path/./")?;
+/// assert_eq!(rule.convert("let dir = \"$$output_dir$$\";")?, "let dir = \"/some/path\";");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PathRule {
+    /// The normalized path the keyword will be replaced with.
+    path: String,
+    /// Rule used to replace the keyword.
+    rule: StringRule,
+}
+
+impl Rule for PathRule {
+    fn name(&self) -> &str {
+        self.rule.name()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        self.rule.convert(template)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.rule.matches_any(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.rule.count_matches(template)
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Path {
+            matches: self.name().to_string(),
+            replace_with: self.path.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for PathRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PathRule({:?} -> {:?})", self.name(), self.path)
+    }
+}
+
+impl PathRule {
+    /// Setup a new rule, failing when the replacement isn't a valid path.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        if replace_with.is_empty() {
+            bail!("path can't be empty");
+        }
+        if replace_with.contains('\0') {
+            bail!(
+                "path `{}` contains a nul byte",
+                replace_with.escape_default()
+            );
+        }
+
+        let path = Path::new(replace_with)
+            .components()
+            .collect::<PathBuf>()
+            .to_string_lossy()
+            .into_owned();
+
+        Ok(Self {
+            rule: StringRule::new(matches, &path)?,
+            path,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn path_rule() -> Result<()> {
+        assert_eq!(
+            PathRule::new("path", "src//rule/")?.convert("\"$$path$$/mod.rs\"")?,
+            "\"src/rule/mod.rs\""
+        );
+        assert_eq!(PathRule::new("path", "/")?.convert("$$path$$")?, "/");
+        assert_eq!(
+            PathRule::new("path", "./a/../b")?.convert("$$path$$")?,
+            "./a/../b"
+        );
+        assert_eq!(
+            PathRule::new("path", "src")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+        assert!(PathRule::new("path", "").is_err());
+        assert!(PathRule::new("path", "a\0b").is_err());
+
+        Ok(())
+    }
+}
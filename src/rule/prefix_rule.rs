@@ -0,0 +1,113 @@
+use std::{fmt, sync::Arc};
+
+use anyhow::{bail, Result};
+use regex::{Captures, Regex};
+
+use crate::Rule;
+
+/// Function used to calculate the replacement from the rest of the keyword.
+type ReplaceFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Replace all keywords starting with a prefix.
+///
+/// This will look for any code containing the `$$prefix..$$` sequence, the replacement is
+/// calculated from the part of the keyword after the prefix.
+/// ```rust
+/// # use replacer::rule::{Rule, PrefixRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = PrefixRule::new("field_", |suffix| format!("my_{}", suffix))?;
+/// assert_eq!(
+///     rule.convert("struct Point { $$field_x$$: i32, $$field_y$$: i32 }")?,
+///     "struct Point { my_x: i32, my_y: i32 }"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PrefixRule {
+    /// The prefix of the keywords that will be matched with.
+    prefix: String,
+    /// Calculates what the keyword will be replaced with.
+    replace_with: ReplaceFn,
+    /// Regex used to find the keywords.
+    regex: Regex,
+}
+
+impl Rule for PrefixRule {
+    fn name(&self) -> &str {
+        &self.prefix
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            (self.replace_with)(&caps["suffix"])
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for PrefixRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrefixRule({:?} -> <fn>)", self.prefix)
+    }
+}
+
+impl PrefixRule {
+    /// Setup a new rule.
+    ///
+    /// The prefix can only contain characters that are valid in a Rust identifier.
+    pub fn new<F>(prefix: &str, replace_with: F) -> Result<Self>
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            bail!("`{}` is not a valid prefix", prefix);
+        }
+        let regex = Regex::new(&format!(
+            r"\$\${}(?P<suffix>\w+)\$\$",
+            regex::escape(prefix)
+        ))?;
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            replace_with: Arc::new(replace_with),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn prefix_rule() -> Result<()> {
+        let rule = PrefixRule::new("field_", |suffix| suffix.to_uppercase())?;
+        assert_eq!(
+            rule.convert("$$field_a$$ $$field_$$ $$other_b$$ $$field_c_d$$")?,
+            "A $$field_$$ $$other_b$$ C_D"
+        );
+        assert!(rule.matches_any("$$field_a$$"));
+        assert!(!rule.matches_any("Hello world!"));
+
+        assert!(PrefixRule::new("", |suffix| suffix.to_string()).is_err());
+        assert!(PrefixRule::new("field-", |suffix| suffix.to_string()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::{Rule, RuleSpec};
+
+/// Replace everything matching a regular expression.
+///
+/// Capture groups can be referenced in the replacement, like `$1` or `${name}`.
+/// ```rust
+/// # use replacer::rule::{Rule, RegexRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = RegexRule::new(r"let (\w+) = (\d+);", "let $1: u32 = $2;")?;
+/// assert_eq!(rule.convert("let x = 5;")?, "let x: u32 = 5;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RegexRule {
+    /// What the matches will be replaced with.
+    replace_with: String,
+    /// Regex used to find the matches.
+    regex: Regex,
+}
+
+impl Rule for RegexRule {
+    fn name(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, replace_with);
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Regex {
+            pattern: self.regex.as_str().to_string(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for RegexRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RegexRule({:?} -> {:?})",
+            self.regex.as_str(),
+            self.replace_with
+        )
+    }
+}
+
+impl RegexRule {
+    /// Setup a new rule.
+    pub fn new(pattern: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)?;
+
+        Ok(Self {
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn regex_rule() -> Result<()> {
+        assert_eq!(
+            RegexRule::new(r"(?P<first>\w+) (?P<second>\w+)", "${second} ${first}")?
+                .convert("world Hello")?,
+            "Hello world"
+        );
+        assert_eq!(
+            RegexRule::new(r"\d+", "number")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+        assert!(RegexRule::new(r"(", "number").is_err());
+
+        Ok(())
+    }
+}
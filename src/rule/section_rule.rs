@@ -0,0 +1,141 @@
+use std::fmt;
+
+use anyhow::{bail, Result};
+use regex::{Captures, Regex};
+
+use crate::{rule::indent, Rule, RuleSpec};
+
+/// Replace everything between a `// BEGIN:name` and a `// END:name` comment.
+///
+/// The lines containing the comments are also replaced. Every line of the replacement gets the
+/// indentation of the `BEGIN` comment.
+/// ```rust
+/// # use replacer::rule::{Rule, SectionRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = SectionRule::new("body", "let x = compute();\nprintln!(\"{}\", x);")?;
+/// assert_eq!(
+///     rule.convert("fn main() {\n    // BEGIN:body\n    todo!();\n    // END:body\n}")?,
+///     "fn main() {\n    let x = compute();\n    println!(\"{}\", x);\n}"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SectionRule {
+    /// The name after `// BEGIN:` and `// END:` that will be matched with.
+    matches: String,
+    /// What the section will be replaced with.
+    replace_with: String,
+    /// Regex used to find the section.
+    regex: Regex,
+    /// Regex used to find the start of a section, to detect sections that aren't closed.
+    begin: Regex,
+}
+
+impl Rule for SectionRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}{}",
+                indent(&self.replace_with, &caps["indentation"]),
+                &caps["newline"]
+            )
+        });
+
+        if let Some(begin) = self.begin.find(&replace) {
+            bail!(
+                "section `{}` at byte {} is missing the `// END:{}` comment",
+                self.matches,
+                begin.start(),
+                self.matches
+            );
+        }
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        // An unclosed section is also a match, applying the rule will fail on it
+        self.begin.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.begin.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Section {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for SectionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SectionRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl SectionRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let name = regex::escape(matches);
+        let regex = Regex::new(&format!(
+            r"(?ms)^(?P<indentation>[ \t]*)//[ \t]*BEGIN:{name}\b.*?^[ \t]*//[ \t]*END:{name}\b[^\n]*?(?P<newline>\r?\n|\z)",
+            name = name
+        ))?;
+        let begin = Regex::new(&format!(r"(?m)^[ \t]*//[ \t]*BEGIN:{}\b", name))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+            begin,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn section_rule() -> Result<()> {
+        assert_eq!(
+            SectionRule::new("replace", "a();\n\nb();")?.convert(
+                "fn main() {\r\n\t// BEGIN:replace the calls\r\n\t// BEGIN:replace_other\r\n\tc();\r\n\t// END:replace\r\n}\n// BEGIN:replace\n// END:replace"
+            )?,
+            "fn main() {\r\n\ta();\n\n\tb();\r\n}\na();\n\nb();"
+        );
+        assert_eq!(
+            SectionRule::new("replace", "a();")?.count_matches(
+                "// BEGIN:replace\n// END:replace\n// BEGIN:replace\n// END:replace"
+            ),
+            2
+        );
+        assert!(SectionRule::new("replace", "a();")?
+            .convert("// BEGIN:replace\n// END:replace\n// BEGIN:replace\nc();")
+            .is_err());
+        assert_eq!(
+            SectionRule::new("replace", "a();")?.convert("// END:replace\nHello world!")?,
+            "// END:replace\nHello world!"
+        );
+
+        Ok(())
+    }
+}
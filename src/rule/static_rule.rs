@@ -0,0 +1,149 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust static item with a placeholder static item that can be compiled.
+///
+/// ```rust
+/// // Private
+/// replacer::rust_static!(replace_with_static; PLACEHOLDER: usize = 0;);
+/// // Public
+/// replacer::rust_static!(pub replace_with_other_static; OTHER_PLACEHOLDER: [u8; 2] = [0; 2];);
+/// ```
+#[macro_export]
+macro_rules! rust_static {
+    // Private
+    ($_name:ident; $placeholder:ident: $ty:ty = $value:expr;) => {
+        #[allow(dead_code)]
+        static $placeholder: $ty = $value;
+    };
+    // Public
+    (pub $_name:ident; $placeholder:ident: $ty:ty = $value:expr;) => {
+        #[allow(dead_code)]
+        pub static $placeholder: $ty = $value;
+    };
+}
+
+/// Replace a Rust static item.
+///
+/// The mutability is taken from the replacement, so `mut` should be part of it for a
+/// `static mut` item.
+/// ```rust
+/// # use replacer::rule::{Rule, StaticRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = StaticRule::new("counter", "mut COUNTER: AtomicUsize = AtomicUsize::new(0)")?;
+/// assert_eq!(rule.convert("replacer::rust_static! {counter; PLACEHOLDER: usize = 0;}")?,
+///     "static mut COUNTER: AtomicUsize = AtomicUsize::new(0);");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StaticRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for StaticRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}static {};",
+                &caps.name("pub").map_or("", |cap| cap.as_str()),
+                replace_with,
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Static {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for StaticRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "StaticRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl StaticRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_static!\s*[\({{](?P<pub>pub )?{};{};[\)}}];?",
+            matches,
+            tokens_pattern()
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn static_rule() -> Result<()> {
+        assert_eq!(
+            StaticRule::new("replace", "MAX_SIZE: usize = 1024")?
+                .convert("replacer::rust_static! {replace; PLACEHOLDER: usize = 0;}")?,
+            "static MAX_SIZE: usize = 1024;"
+        );
+        assert_eq!(
+            StaticRule::new("replace", "mut COUNTER: usize = 0")?
+                .convert("replacer::rust_static! {pub replace; PLACEHOLDER: usize = 0;}")?,
+            "pub static mut COUNTER: usize = 0;"
+        );
+        assert_eq!(
+            StaticRule::new("replace", "MAX_SIZE: usize = 1024")?.convert(
+                "replacer::rust_static!(replace; PLACEHOLDER: Buffer<{ 4 * 4 }, [u8; 4]> = Buffer::new([0; 4]););"
+            )?,
+            "static MAX_SIZE: usize = 1024;"
+        );
+        assert_eq!(
+            StaticRule::new("replace", "MAX_SIZE: usize = 1024")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -1,45 +1,532 @@
+use std::{fmt, io::Write, sync::Arc};
+
 use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{rule::validate_identifier, Rule, RuleSpec};
 
-use crate::Rule;
+/// Function used to calculate the replacement from the name of the keyword.
+type ReplaceFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// What a keyword will be replaced with.
+#[derive(Clone)]
+enum Replacement {
+    /// A fixed string.
+    Static(String),
+    /// A string calculated for every match.
+    Fn(ReplaceFn),
+}
 
 /// Replace a string inside another string.
 ///
 /// This will look for any code containing the `${..}` sequence where `..` is
 /// filled with the matches.
+///
+/// The casing of the replacement can be changed by adding a modifier to the keyword:
+/// `$$name:upper$$`, `$$name:lower$$`, `$$name:snake$$` or `$$name:pascal$$`.
+///
+/// When the replacement contains multiple lines, every line after the first is indented like
+/// the line containing the keyword.
+///
+/// A keyword can be escaped with an extra `$` on both sides: `$$$name$$$` isn't replaced but
+/// becomes `$$name$$`.
 /// ```rust
 /// # use replacer::rule::{Rule, StringRule};
 /// # fn main() -> anyhow::Result<()> {
 /// let rule = StringRule::new("replace", "world")?;
 /// assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello world!");
+///
+/// let rule = StringRule::new("name", "my_struct")?;
+/// assert_eq!(
+///     rule.convert("struct $$name:pascal$$; const $$name:upper$$: u8 = 0;")?,
+///     "struct MyStruct; const MY_STRUCT: u8 = 0;"
+/// );
+///
+/// let rule = StringRule::new("body", "let a = 1;\nprintln!(\"{}\", a);")?;
+/// assert_eq!(
+///     rule.convert("fn main() {\n    $$body$$\n}")?,
+///     "fn main() {\n    let a = 1;\n    println!(\"{}\", a);\n}"
+/// );
+///
+/// let rule = StringRule::new("replace", "world")?;
+/// assert_eq!(rule.convert("Hello $$replace$$, $$$replace$$$")?, "Hello world, $$replace$$");
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct StringRule {
     /// The keyword that will be matched with.
     /// This is the `${..}` part in the string.
     matches: String,
     /// What the keyword will be replaced with.
-    replace_with: String,
+    replace_with: Replacement,
+    /// Regex used to find the keyword when it can't be matched literally.
+    regex: Option<Regex>,
+    /// Regex used to find the keyword with a case modifier, like `$$name:upper$$`.
+    modifiers: Regex,
+    /// Regex used to find escaped keywords, like `$$$name$$$`.
+    escapes: Regex,
+    /// Whether the casing of the keyword is ignored.
+    case_insensitive: bool,
+    /// Only replace the keyword when it's not directly surrounded by other placeholder text.
+    whole_word: bool,
+    /// Maximum amount of keywords to replace, `None` replaces all of them.
+    count: Option<usize>,
 }
 
 impl Rule for StringRule {
+    fn name(&self) -> &str {
+        // Strip the `$$` markers
+        &self.matches[2..self.matches.len() - 2]
+    }
+
     fn convert(&self, template: &str) -> Result<String> {
-        Ok(template.replace(&self.matches, &self.replace_with))
+        let converted = match &self.regex {
+            Some(regex) => regex
+                .replace_all(template, |caps: &Captures| {
+                    let found = &caps[0];
+                    let start = caps.get(0).map_or(0, |cap| cap.start());
+                    if self.is_replaceable(template, start, found) {
+                        indent_lines(template, start, &self.replacement())
+                    } else {
+                        found.to_string()
+                    }
+                })
+                .into_owned(),
+            None => {
+                let mut result = String::with_capacity(template.len());
+                self.replace_literal(template, |part| {
+                    result.push_str(part);
+
+                    Ok(())
+                })?;
+
+                result
+            }
+        };
+
+        // Replace the keywords with a case modifier, like `$$name:upper$$`
+        let converted = self
+            .modifiers
+            .replace_all(&converted, |caps: &Captures| {
+                let found = &caps[0];
+                let start = caps.get(0).map_or(0, |cap| cap.start());
+                if self.is_replaceable(&converted, start, found) {
+                    indent_lines(
+                        &converted,
+                        start,
+                        &convert_case(&caps["case"], &self.replacement()),
+                    )
+                } else {
+                    found.to_string()
+                }
+            })
+            .into_owned();
+
+        // Remove the extra `$` from escaped keywords, like `$$$name$$$`
+        let converted = self
+            .escapes
+            .replace_all(&converted, |caps: &Captures| {
+                let found = caps.get(0).expect("regex always has a full match");
+                if is_escaped(&converted, found.start() + 1, found.end() - 1) {
+                    caps["keyword"].to_string()
+                } else {
+                    found.as_str().to_string()
+                }
+            })
+            .into_owned();
+
+        Ok(converted)
+    }
+
+    fn convert_to_writer(&self, template: &str, output: &mut dyn Write) -> Result<()> {
+        // The keywords with a case modifier and the escaped keywords need extra passes
+        if self.regex.is_some()
+            || self.modifiers.is_match(template)
+            || self.escapes.is_match(template)
+        {
+            output.write_all(self.convert(template)?.as_bytes())?;
+
+            return Ok(());
+        }
+
+        self.replace_literal(template, |part| Ok(output.write_all(part.as_bytes())?))
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        let matches = match &self.regex {
+            Some(regex) => regex
+                .find_iter(template)
+                .any(|found| self.is_replaceable(template, found.start(), found.as_str())),
+            None => template.contains(&self.matches),
+        };
+
+        matches
+            || self
+                .modifiers
+                .find_iter(template)
+                .any(|found| self.is_replaceable(template, found.start(), found.as_str()))
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        let matches = match &self.regex {
+            Some(regex) => regex
+                .find_iter(template)
+                .filter(|found| self.is_replaceable(template, found.start(), found.as_str()))
+                .count(),
+            None => template
+                .match_indices(&self.matches)
+                .filter(|(start, found)| self.is_replaceable(template, *start, found))
+                .take(self.count.unwrap_or(usize::MAX))
+                .count(),
+        };
+
+        matches
+            + self
+                .modifiers
+                .find_iter(template)
+                .filter(|found| self.is_replaceable(template, found.start(), found.as_str()))
+                .count()
+    }
+
+    fn is_suspicious(&self) -> bool {
+        // Replacing a placeholder with nothing is almost always a mistake
+        matches!(&self.replace_with, Replacement::Static(replace_with) if replace_with.is_empty())
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        match &self.replace_with {
+            Replacement::Static(replace_with) => Some(RuleSpec::String {
+                matches: self.name().to_string(),
+                replace_with: replace_with.clone(),
+                case_insensitive: self.case_insensitive,
+                whole_word: self.whole_word,
+                count: self.count,
+            }),
+            // Functions can't be serialized
+            Replacement::Fn(_) => None,
+        }
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for StringRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.replace_with {
+            Replacement::Static(replace_with) => {
+                write!(f, "StringRule({:?} -> {:?})", self.name(), replace_with)
+            }
+            Replacement::Fn(_) => write!(f, "StringRule({:?} -> <fn>)", self.name()),
+        }
     }
 }
 
 impl StringRule {
     /// Setup a new rule.
+    ///
+    /// The keyword must be a valid Rust identifier.
+    /// ```rust
+    /// # use replacer::rule::StringRule;
+    /// assert!(StringRule::new("a b c", "world").is_err());
+    /// ```
     pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        validate_identifier(matches)?;
+
         Ok(Self {
             matches: format!("$${}$$", matches),
-            replace_with: replace_with.to_string(),
+            replace_with: Replacement::Static(replace_with.to_string()),
+            regex: None,
+            modifiers: modifier_regex(matches, false)?,
+            escapes: escape_regex(matches, false)?,
+            case_insensitive: false,
+            whole_word: false,
+            count: None,
+        })
+    }
+
+    /// Setup a new rule that ignores the casing of the keyword.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new_case_insensitive("replace", "world")?;
+    /// assert_eq!(rule.convert("Hello $$REPLACE$$ and $$Replace$$!")?, "Hello world and world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_case_insensitive(matches: &str, replace_with: &str) -> Result<Self> {
+        validate_identifier(matches)?;
+        let matches = format!("$${}$$", matches);
+        let regex = Regex::new(&format!("(?i){}", regex::escape(&matches)))?;
+
+        Ok(Self {
+            modifiers: modifier_regex(&matches[2..matches.len() - 2], true)?,
+            escapes: escape_regex(&matches[2..matches.len() - 2], true)?,
+            matches,
+            replace_with: Replacement::Static(replace_with.to_string()),
+            regex: Some(regex),
+            case_insensitive: true,
+            whole_word: false,
+            count: None,
+        })
+    }
+
+    /// Setup a new rule where the replacement is calculated for every match.
+    ///
+    /// The function receives the name of the keyword.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new_with_fn("replace", |name| name.to_uppercase())?;
+    /// assert_eq!(rule.convert("Hello $$replace$$!")?, "Hello REPLACE!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_fn<F>(matches: &str, replace_with: F) -> Result<Self>
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        validate_identifier(matches)?;
+        let matches = format!("$${}$$", matches);
+        let regex = Regex::new(&regex::escape(&matches))?;
+
+        Ok(Self {
+            modifiers: modifier_regex(&matches[2..matches.len() - 2], false)?,
+            escapes: escape_regex(&matches[2..matches.len() - 2], false)?,
+            matches,
+            replace_with: Replacement::Fn(Arc::new(replace_with)),
+            regex: Some(regex),
+            case_insensitive: false,
+            whole_word: false,
+            count: None,
+        })
+    }
+
+    /// Setup a new rule that only matches the exact keyword.
+    ///
+    /// Keywords that are directly followed or preceded by other identifier characters or `$`
+    /// signs, like in `$$prefix$$replace$$`, are skipped.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new_whole_word("replace", "world")?;
+    /// assert_eq!(
+    ///     rule.convert("Hello $$replace$$ and $$prefix$$replace$$!")?,
+    ///     "Hello world and $$prefix$$replace$$!"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_whole_word(matches: &str, replace_with: &str) -> Result<Self> {
+        validate_identifier(matches)?;
+        let matches = format!("$${}$$", matches);
+        let regex = Regex::new(&regex::escape(&matches))?;
+
+        Ok(Self {
+            modifiers: modifier_regex(&matches[2..matches.len() - 2], false)?,
+            escapes: escape_regex(&matches[2..matches.len() - 2], false)?,
+            matches,
+            replace_with: Replacement::Static(replace_with.to_string()),
+            regex: Some(regex),
+            case_insensitive: false,
+            whole_word: true,
+            count: None,
+        })
+    }
+
+    /// Setup a new rule that only replaces the first `count` keywords.
+    ///
+    /// Keywords with a case modifier, like `$$name:upper$$`, are always replaced.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, StringRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = StringRule::new_counted("replace", "world", 1)?;
+    /// assert_eq!(
+    ///     rule.convert("Hello $$replace$$, write $$replace$$ to add a placeholder")?,
+    ///     "Hello world, write $$replace$$ to add a placeholder"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_counted(matches: &str, replace_with: &str, count: usize) -> Result<Self> {
+        Ok(Self {
+            count: Some(count),
+            ..Self::new(matches, replace_with)?
+        })
+    }
+
+    /// Replace the keyword when it can be matched literally, every part of the result is
+    /// passed to `push` in order.
+    fn replace_literal<F>(&self, template: &str, mut push: F) -> Result<()>
+    where
+        F: FnMut(&str) -> Result<()>,
+    {
+        let mut last_end = 0;
+        for (start, found) in template
+            .match_indices(&self.matches)
+            .filter(|(start, found)| self.is_replaceable(template, *start, found))
+            .take(self.count.unwrap_or(usize::MAX))
+        {
+            push(&template[last_end..start])?;
+            push(&indent_lines(template, start, &self.replacement()))?;
+            last_end = start + found.len();
+        }
+
+        push(&template[last_end..])
+    }
+
+    /// Whether a match should be replaced, escaped keywords and keywords that are part of a
+    /// larger word aren't.
+    fn is_replaceable(&self, template: &str, start: usize, found: &str) -> bool {
+        !is_escaped(template, start, start + found.len())
+            && self.is_whole_word(template, start, found)
+    }
+
+    /// Whether a match isn't part of a larger word, always true when not matching whole words.
+    fn is_whole_word(&self, template: &str, start: usize, found: &str) -> bool {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+
+        !self.whole_word
+            || (!template[..start].ends_with(is_word)
+                && !template[start + found.len()..].starts_with(is_word))
+    }
+
+    /// Get the string a single match will be replaced with.
+    fn replacement(&self) -> String {
+        match &self.replace_with {
+            Replacement::Static(replace_with) => replace_with.clone(),
+            Replacement::Fn(replace_with) => replace_with(self.name()),
+        }
+    }
+}
+
+/// Whether the keyword at the range is escaped with an extra `$` on both sides.
+///
+/// A `$` run next to the keyword can also close or open a neighbouring placeholder, like in
+/// `$$a$$$$b$$`, those use two `$` each so only an odd amount contains an escape character.
+fn is_escaped(template: &str, start: usize, end: usize) -> bool {
+    let before = template[..start]
+        .bytes()
+        .rev()
+        .take_while(|byte| *byte == b'$')
+        .count();
+    let after = template[end..]
+        .bytes()
+        .take_while(|byte| *byte == b'$')
+        .count();
+
+    before % 2 == 1 && after % 2 == 1
+}
+
+/// Create a regex matching the keyword followed by a case modifier, like `$$name:upper$$`.
+fn modifier_regex(matches: &str, case_insensitive: bool) -> Result<Regex> {
+    Ok(Regex::new(&format!(
+        r"{}\$\${}:(?P<case>upper|lower|snake|pascal)\$\$",
+        if case_insensitive { "(?i)" } else { "" },
+        regex::escape(matches)
+    ))?)
+}
+
+/// Indent every line after the first line of the replacement like the line of the keyword.
+///
+/// Empty lines aren't indented so no trailing whitespace is added.
+fn indent_lines(template: &str, start: usize, replacement: &str) -> String {
+    if !replacement.contains('\n') {
+        return replacement.to_string();
+    }
+
+    let line_start = template[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line = &template[line_start..start];
+    let indentation = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+
+    replacement
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{}", indentation, line)
+            }
         })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Create a regex matching an escaped keyword, like `$$$name$$$` or `$$$name:upper$$$`.
+///
+/// The keyword without the surrounding `$` is captured in the `keyword` group.
+fn escape_regex(matches: &str, case_insensitive: bool) -> Result<Regex> {
+    Ok(Regex::new(&format!(
+        r"{}\$(?P<keyword>\$\${}(?::(?:upper|lower|snake|pascal))?\$\$)\$",
+        if case_insensitive { "(?i)" } else { "" },
+        regex::escape(matches)
+    ))?)
+}
+
+/// Change the casing of the replacement according to a modifier.
+fn convert_case(case: &str, replacement: &str) -> String {
+    match case.to_lowercase().as_str() {
+        "upper" => replacement.to_uppercase(),
+        "lower" => replacement.to_lowercase(),
+        "snake" => split_words(replacement)
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        "pascal" => split_words(replacement)
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or(String::new(), |first| {
+                    first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                        .collect()
+                })
+            })
+            .collect(),
+        _ => replacement.to_string(),
     }
 }
 
+/// Split a string into words on non-alphanumeric characters and lowercase to uppercase changes.
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            previous_lowercase = false;
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lowercase = c.is_lowercase() || c.is_numeric();
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use anyhow::Result;
 
     use super::*;
@@ -58,6 +545,113 @@ mod tests {
             StringRule::new("replace", "world")?.convert("Hello $$replace$$, bye $$replace$$!")?,
             "Hello world, bye world!"
         );
+        assert_eq!(
+            StringRule::new_case_insensitive("replace", "$1 world")?
+                .convert("Hello $$REPLACE$$, bye $$replace$$!")?,
+            "Hello $1 world, bye $1 world!"
+        );
+
+        let counter = AtomicUsize::new(0);
+        assert_eq!(
+            StringRule::new_with_fn("replace", move |name| format!(
+                "{}{}",
+                name,
+                counter.fetch_add(1, Ordering::SeqCst)
+            ))?
+            .convert("Hello $$replace$$, bye $$replace$$!")?,
+            "Hello replace0, bye replace1!"
+        );
+
+        assert_eq!(
+            StringRule::new_whole_word("foo", "bar")?
+                .convert("$$foo$$ $$foo$$bar$$ $$x$$foo$$ ($$foo$$)")?,
+            "bar $$foo$$bar$$ $$x$$foo$$ (bar)"
+        );
+        assert!(!StringRule::new_whole_word("foo", "bar")?.matches_any("$$x$$foo$$"));
+
+        assert_eq!(
+            StringRule::new("replace", "HelloWorld")?.convert(
+                "$$replace:upper$$ $$replace:lower$$ $$replace:snake$$ $$replace:pascal$$ $$replace:other$$"
+            )?,
+            "HELLOWORLD helloworld hello_world HelloWorld $$replace:other$$"
+        );
+        assert_eq!(
+            StringRule::new_case_insensitive("replace", "hello world")?
+                .convert("$$REPLACE:Pascal$$ $$Replace:snake$$")?,
+            "HelloWorld hello_world"
+        );
+        assert!(StringRule::new("replace", "world")?.matches_any("Hello $$replace:upper$$"));
+
+        assert_eq!(
+            StringRule::new("body", "a();\n\nb();\r\nc();")?
+                .convert("fn main() {\n\tif x {\n\t    $$body$$\n\t}\n}\n$$body$$")?,
+            "fn main() {\n\tif x {\n\t    a();\n\n\t    b();\r\n\t    c();\n\t}\n}\na();\n\nb();\r\nc();"
+        );
+        assert_eq!(
+            StringRule::new_case_insensitive("body", "a\nb")?
+                .convert("  let x = $$BODY$$ + $$body:upper$$;")?,
+            "  let x = a\n  b + A\n  B;"
+        );
+
+        assert_eq!(
+            StringRule::new_counted("replace", "world", 2)?
+                .convert("$$replace$$ $$replace$$ $$replace$$ $$replace:upper$$")?,
+            "world world $$replace$$ WORLD"
+        );
+        assert_eq!(
+            StringRule::new_counted("replace", "world", 2)?
+                .count_matches("$$replace$$ $$replace$$ $$replace$$"),
+            2
+        );
+        assert_eq!(
+            StringRule::new_counted("replace", "world", 0)?.convert("$$replace$$")?,
+            "$$replace$$"
+        );
+
+        assert_eq!(
+            StringRule::new("replace", "world")?
+                .convert("$$$replace$$$ $$replace$$ $$$replace:upper$$$ $$replace:upper$$")?,
+            "$$replace$$ world $$replace:upper$$ WORLD"
+        );
+        assert_eq!(
+            StringRule::new_case_insensitive("replace", "world")?
+                .convert("$$$REPLACE$$$ $$Replace$$")?,
+            "$$REPLACE$$ world"
+        );
+        assert_eq!(
+            StringRule::new_counted("replace", "world", 1)?
+                .convert("$$$replace$$$ $$replace$$ $$replace$$")?,
+            "$$replace$$ world $$replace$$"
+        );
+        assert_eq!(
+            StringRule::new("replace", "world")?.count_matches("$$$replace$$$ $$replace$$"),
+            1
+        );
+
+        let adjacent = ["b", "a", "c"].iter().zip(["Y", "X", "Z"]).try_fold(
+            "$$a$$$$b$$$$c$$".to_string(),
+            |template, (matches, replace_with)| {
+                StringRule::new(matches, replace_with)?.convert(&template)
+            },
+        )?;
+        assert_eq!(adjacent, "XYZ");
+        assert_eq!(
+            StringRule::new("b", "Y")?.convert("$$a$$$$$b$$$")?,
+            "$$a$$$$b$$"
+        );
+        assert_eq!(
+            StringRule::new("b", "Y")?.convert("$$$b$$$$$c$$ $$b$$$$c$$")?,
+            "$$b$$$$c$$ Y$$c$$"
+        );
+
+        assert!(StringRule::new("replace", "")?.is_suspicious());
+        assert!(!StringRule::new("replace", "world")?.is_suspicious());
+
+        assert!(StringRule::new("", "world").is_err());
+        assert!(StringRule::new("1replace", "world").is_err());
+        assert!(StringRule::new("re-place", "world").is_err());
+        assert!(StringRule::new_case_insensitive("re place", "world").is_err());
+        assert!(StringRule::new("_replace_1", "world").is_ok());
 
         Ok(())
     }
@@ -1,7 +1,12 @@
+use std::fmt;
+
 use anyhow::Result;
 use regex::{Captures, Regex};
 
-use crate::Rule;
+use crate::{
+    rule::{nested_pattern, string_literals, tokens_pattern},
+    Rule, RuleSpec,
+};
 
 /// Template macro for replacing a Rust struct with a placeholder struct that can be compiled.
 ///
@@ -10,75 +15,241 @@ use crate::Rule;
 /// replacer::rust_struct!(replace_with_struct; Point2D { x: i32, y: i32 };);
 /// // Public
 /// replacer::rust_struct!(pub replace_with_other_struct; Point3D { x: i32, y: i32, z: i32 };);
+/// // Restricted visibility
+/// replacer::rust_struct!(pub(crate) replace_with_crate_struct; Point1D { x: i32 };);
 /// // With a lifetime
 /// replacer::rust_struct!(replace_with_struct; Point4D<'a> { x: i32, y: &'a i32, z: i32, w: i32 };);
+/// // With generic type parameters
+/// replacer::rust_struct!(replace_with_struct; Container<T: Clone> { inner: T };);
+/// replacer::rust_struct!(pub replace_with_struct; Pair<T, U: std::fmt::Debug> { a: T, b: U };);
+/// // Tuple
+/// replacer::rust_struct!(replace_with_tuple_struct; Wrapper(i32, String););
+/// // Unit
+/// replacer::rust_struct!(pub replace_with_unit_struct; Marker;);
 /// ```
 #[macro_export]
 macro_rules! rust_struct {
-    // No lifetime, private
-    ($_name:ident; $placeholder:ident {$($element: ident: $ty: ty),*};) => {
-        struct $placeholder { $($element: $ty),* }
+    // No lifetime
+    ($vis:vis $_name:ident; $placeholder:ident {$($element: ident: $ty: ty),*};) => {
+        $vis struct $placeholder { $($element: $ty),* }
+    };
+    // Lifetime
+    ($vis:vis $_name:ident; $placeholder:ident<$lifetime:lifetime>{$($element: ident: $ty: ty),*};) => {
+        $vis struct $placeholder<$lifetime> { $($element: $ty),* }
+    };
+    // One type parameter
+    ($vis:vis $_name:ident; $placeholder:ident<$t:ident $(: $t_bound:path)?>{$($element: ident: $ty: ty),*};) => {
+        $vis struct $placeholder<$t $(: $t_bound)?> { $($element: $ty),* }
     };
-    // No lifetime, public
-    (pub $_name:ident; $placeholder:ident {$($element: ident: $ty: ty),*};) => {
-        pub struct $placeholder { $($element: $ty),* }
+    // Two type parameters
+    ($vis:vis $_name:ident; $placeholder:ident<$t:ident $(: $t_bound:path)?, $u:ident $(: $u_bound:path)?>{$($element: ident: $ty: ty),*};) => {
+        $vis struct $placeholder<$t $(: $t_bound)?, $u $(: $u_bound)?> { $($element: $ty),* }
     };
-    // Lifetime, private
-    ($_name:ident; $placeholder:ident<$lifetime:lifetime>{$($element: ident: $ty: ty),*};) => {
-        struct $placeholder<$lifetime> { $($element: $ty),* }
+    // Tuple
+    ($vis:vis $_name:ident; $placeholder:ident($($ty: ty),*);) => {
+        $vis struct $placeholder($($ty),*);
     };
-    // Lifetime, public
-    (pub $_name:ident; $placeholder:ident<$lifetime:lifetime>{$($element: ident: $ty: ty),*};) => {
-        pub struct $placeholder<$lifetime> { $($element: $ty),* }
+    // Unit
+    ($vis:vis $_name:ident; $placeholder:ident;) => {
+        $vis struct $placeholder;
     };
 }
 
 /// Replace a Rust struct.
+///
+/// The replacement can be a struct with named fields like `Point { x: i32 }`, a tuple struct
+/// like `Wrapper(i32)` or a unit struct like `Marker`.
+///
+/// Besides the `rust_struct!` macro, structs marked with the
+/// `#[replacer::template_struct(name = "..")]` attribute are also replaced.
+///
+/// Attributes like `#[derive(Debug)]` on the placeholder struct are kept by default, use
+/// [`StructRule::keep_attributes`] to remove them. Attributes at the start of the replacement,
+/// like `#[repr(C)] Point { x: i32 }`, are placed before the `struct` keyword.
 /// ```rust
 /// # use replacer::rule::{Rule, StructRule};
 /// # fn main() -> anyhow::Result<()> {
 /// let rule = StructRule::new("point", "Point3D { x: i32, y: i32, z: i32 }")?;
 /// assert_eq!(rule.convert("replacer::rust_struct!(point; Point2D{ x: i32, y: i32};}")?,
 ///     "struct Point3D { x: i32, y: i32, z: i32 }");
+///
+/// let rule = StructRule::new("point", "Wrapper(i32)")?;
+/// assert_eq!(rule.convert("replacer::rust_struct!(point; Point2D{ x: i32, y: i32};);")?,
+///     "struct Wrapper(i32);");
+///
+/// let rule = StructRule::new("point", "Point3D { x: i32, y: i32, z: i32 }")?.keep_attributes(false);
+/// assert_eq!(rule.convert("#[derive(Debug)]\nreplacer::rust_struct!(point; Point2D{ x: i32, y: i32};}")?,
+///     "struct Point3D { x: i32, y: i32, z: i32 }");
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct StructRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
     /// What the keyword will be replaced with.
     replace_with: String,
     /// Regex used to find the macro.
     regex: Regex,
+    /// Whether the attributes of the placeholder struct are kept.
+    keep_attributes: bool,
 }
 
 impl Rule for StructRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
     fn convert(&self, template: &str) -> Result<String> {
         let replace_with: &str = &self.replace_with;
+        let literals = string_literals(template);
         let replace = self.regex.replace_all(template, |caps: &Captures| {
+            // Don't touch invocations inside string literals
+            let start = caps.get(0).map_or(0, |cap| cap.start());
+            if literals.iter().any(|literal| literal.contains(&start)) {
+                return caps[0].to_string();
+            }
+
+            // Tuple and unit structs must be terminated by a semicolon, structs with named fields
+            // can't be, so the semicolon after a parenthesized invocation is dropped for those
+            let terminator = if replace_with.ends_with('}') { "" } else { ";" };
+
+            // The visibility of the placeholder struct is kept
+            let visibility = caps
+                .name("vis")
+                .or_else(|| caps.name("attr_vis"))
+                .map_or(String::new(), |cap| format!("{} ", cap.as_str().trim_end()));
+
+            let attributes = if self.keep_attributes {
+                format!(
+                    "{}{}",
+                    caps.name("attrs").map_or("", |cap| cap.as_str()),
+                    caps.name("attr_attrs").map_or("", |cap| cap.as_str())
+                )
+            } else {
+                String::new()
+            };
+
+            // Attributes of the replacement must be placed before the visibility
+            let (replace_attributes, replace_with) = split_attributes(replace_with);
+
             format!(
-                "{}struct {}",
-                &caps.name("pub").map_or("", |cap| cap.as_str()),
-                replace_with,
+                "{}{}{}struct {}{}",
+                attributes, replace_attributes, visibility, replace_with, terminator
             )
         });
 
         Ok(replace.into_owned())
     }
+
+    fn matches_any(&self, template: &str) -> bool {
+        let literals = string_literals(template);
+
+        self.regex.find_iter(template).any(|found| {
+            !literals
+                .iter()
+                .any(|literal| literal.contains(&found.start()))
+        })
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        let literals = string_literals(template);
+
+        self.regex
+            .find_iter(template)
+            .filter(|found| {
+                !literals
+                    .iter()
+                    .any(|literal| literal.contains(&found.start()))
+            })
+            .count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Struct {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+            keep_attributes: self.keep_attributes,
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for StructRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "StructRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
 }
 
 impl StructRule {
     /// Setup a new rule.
     pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
         let regex = Regex::new(&format!(
-            r"{}[\({{]{}[\)}}]",
-            r"replacer::rust_struct!\s*",
-            format!(r"(?P<pub>pub )?{};[^{{]+\{{[^;]+}};", matches)
+            concat!(
+                r"(?P<attrs>(?:#{attr}\s*)*)",
+                r"(?:replacer::rust_struct!\s*[\({{]\s*(?:(?P<vis>{vis})\s+)?{name}\s*;{tokens};\s*[\)}}];?",
+                r#"|#\[\s*(?:replacer::)?template_struct\s*\(\s*name\s*=\s*"{name}"\s*\)\s*\]"#,
+                r"\s*(?P<attr_attrs>(?:#{attr}\s*)*)",
+                r"(?:(?P<attr_vis>{vis})\s+)?struct\s+[^{{;(]+(?:{braces}|{parens}\s*;|;))"
+            ),
+            name = matches,
+            tokens = tokens_pattern(),
+            braces = nested_pattern(r"\{", r"\}", "{}", 3),
+            parens = nested_pattern(r"\(", r"\)", "()", 3),
+            attr = nested_pattern(r"\[", r"\]", r"\[\]", 3),
+            vis = r"pub(?:\s*\([^)]*\))?",
         ))?;
 
         Ok(Self {
+            matches: matches.to_string(),
             replace_with: replace_with.to_string(),
             regex,
+            keep_attributes: true,
         })
     }
+
+    /// Set whether attributes like `#[derive(Debug)]` on the placeholder struct are kept.
+    ///
+    /// This is `true` by default.
+    pub fn keep_attributes(mut self, keep_attributes: bool) -> Self {
+        self.keep_attributes = keep_attributes;
+
+        self
+    }
+}
+
+/// Split the leading attributes, like `#[repr(C)]`, from the rest of the replacement.
+fn split_attributes(replace_with: &str) -> (&str, &str) {
+    let mut end = 0;
+    while replace_with[end..].starts_with("#[") {
+        let mut depth = 0usize;
+        let closing = replace_with[end..].char_indices().find(|(_, character)| {
+            match character {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => return false,
+            }
+
+            depth == 0
+        });
+        match closing {
+            Some((index, _)) => {
+                let rest = &replace_with[end + index + 1..];
+                end = replace_with.len() - rest.trim_start().len();
+            }
+            // Unclosed attribute, keep it as part of the struct
+            None => break,
+        }
+    }
+
+    replace_with.split_at(end)
 }
 
 #[cfg(test)]
@@ -99,6 +270,104 @@ mod tests {
                 .convert("replacer::rust_struct! {pub replace; Point{ x: i32, y: i32};}")?,
             "pub struct Point2D { x: i32, y: i32 }"
         );
+        assert_eq!(
+            StructRule::new("replace", "Point2D { x: i32, y: i32 }")?.convert(
+                "replacer::rust_struct! {pub(crate) replace; Point{ x: i32, y: i32};}\nreplacer::rust_struct!(pub(in crate::a) replace; Marker;);"
+            )?,
+            "pub(crate) struct Point2D { x: i32, y: i32 }\npub(in crate::a) struct Point2D { x: i32, y: i32 }"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Marker")?.convert(
+                "#[replacer::template_struct(name = \"replace\")]\npub(super) struct Placeholder;"
+            )?,
+            "pub(super) struct Marker;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Pair<A, B> { a: A, b: B }")?.convert(
+                "replacer::rust_struct! {replace; Pair<T: Clone, U>{ a: T, b: Vec<U>};}"
+            )?,
+            "struct Pair<A, B> { a: A, b: B }"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Wrapper(i32, [u8; 4])")?
+                .convert("replacer::rust_struct! {pub replace; Point{ x: i32, y: i32};}")?,
+            "pub struct Wrapper(i32, [u8; 4]);"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Marker")?
+                .convert("replacer::rust_struct!(replace; Point(i32, i32););")?,
+            "struct Marker;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point { x: i32 }")?
+                .convert("replacer::rust_struct!(replace; Marker;);")?,
+            "struct Point { x: i32 }"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point")?.convert(
+                r##"let s = "replacer::rust_struct!(replace; Point;);"; let r = r#"replacer::rust_struct!(replace; Point;);"#;"##
+            )?,
+            r##"let s = "replacer::rust_struct!(replace; Point;);"; let r = r#"replacer::rust_struct!(replace; Point;);"#;"##
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point")?.convert(
+                "let c = '\"'; // \"\nreplacer::rust_struct!(replace; Marker;); let s = \"\\\"\";"
+            )?,
+            "let c = '\"'; // \"\nstruct Point; let s = \"\\\"\";"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point { x: i32 }")?.convert(
+                "#[replacer::template_struct(name = \"replace\")]\npub struct Placeholder<T> { a: Vec<T> }\n#[template_struct(name = \"other\")]\nstruct Other;"
+            )?,
+            "pub struct Point { x: i32 }\n#[template_struct(name = \"other\")]\nstruct Other;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point(i32)")?.convert(
+                "#[replacer::template_struct(name = \"replace\")]\nstruct Placeholder(u8, (u8, u8));"
+            )?,
+            "struct Point(i32);"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point")?.convert(
+                "#[derive(Debug, Clone)]\n#[allow(dead_code)]\nreplacer::rust_struct!(replace; Marker;);"
+            )?,
+            "#[derive(Debug, Clone)]\n#[allow(dead_code)]\nstruct Point;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point")?
+                .keep_attributes(false)
+                .convert(
+                    "#[derive(Debug, Clone)]\n#[allow(dead_code)]\nreplacer::rust_struct!(replace; Marker;);"
+                )?,
+            "struct Point;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "Point")?
+                .keep_attributes(false)
+                .convert(
+                    "#[derive(Debug)]\nstruct Other;\n#[cfg_attr(test, derive(Default))]\n#[replacer::template_struct(name = \"replace\")]\n#[derive(Clone)]\nstruct Marker;"
+                )?,
+            "#[derive(Debug)]\nstruct Other;\nstruct Point;"
+        );
+        assert_eq!(
+            StructRule::new("replace", "#[repr(C)] #[cfg_attr(test, derive(Debug))]\nPoint { x: u32 }")?
+                .convert("#[derive(Clone)]\nreplacer::rust_struct! {pub replace; Marker;}")?,
+            "#[derive(Clone)]\n#[repr(C)] #[cfg_attr(test, derive(Debug))]\npub struct Point { x: u32 }"
+        );
+        assert_eq!(
+            StructRule::new("replace", "#[repr(transparent)] Wrapper(u32)")?.convert(
+                "#[replacer::template_struct(name = \"replace\")]\npub struct Placeholder;"
+            )?,
+            "#[repr(transparent)] pub struct Wrapper(u32);"
+        );
+        assert!(!StructRule::new("replace", "Point")?
+            .matches_any("\"replacer::rust_struct!(replace; Point;);\""));
+        assert_eq!(
+            StructRule::new("replace", "Point")?.count_matches(
+                "replacer::rust_struct!(replace; Marker;); let s = \"replacer::rust_struct!(replace; Point;);\";"
+            ),
+            1
+        );
         assert_eq!(
             StructRule::new("replace", "i32")?.convert("Hello world!")?,
             "Hello world!"
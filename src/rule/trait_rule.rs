@@ -0,0 +1,137 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{NoExpand, Regex};
+
+use crate::{rule::nested_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing a Rust trait with a placeholder trait that can be compiled.
+///
+/// ```rust
+/// replacer::rust_trait!(replace_with_trait; trait Placeholder { fn foo(&self); });
+/// ```
+#[macro_export]
+macro_rules! rust_trait {
+    ($_name:ident; $($placeholder:tt)*) => {
+        $($placeholder)*
+    };
+}
+
+/// Replace a Rust trait definition.
+///
+/// The replacement is inserted verbatim, so it should contain the full trait definition.
+/// ```rust
+/// # use replacer::rule::{Rule, TraitRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = TraitRule::new("shape", "trait Shape: Debug { fn area(&self) -> f64 { 0.0 } }")?;
+/// assert_eq!(rule.convert("replacer::rust_trait!(shape; trait Placeholder { fn foo(&self); });")?,
+///     "trait Shape: Debug { fn area(&self) -> f64 { 0.0 } }");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TraitRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for TraitRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self
+            .regex
+            .replace_all(template, NoExpand(&self.replace_with));
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Trait {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for TraitRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TraitRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl TraitRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_trait!\s*[\({{]{};\s*(?:pub(?:\([^)]*\))?\s+)?trait\s[^{{;]+{}\s*[\)}}];?",
+            matches,
+            // Default method bodies contain nested braces
+            nested_pattern(r"\{", r"\}", "{}", 4)
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn trait_rule() -> Result<()> {
+        assert_eq!(
+            TraitRule::new("replace", "trait Shape {}")?
+                .convert("replacer::rust_trait! {replace; trait Placeholder { fn foo(&self); }}")?,
+            "trait Shape {}"
+        );
+        assert_eq!(
+            TraitRule::new("replace", "pub trait Shape {}")?.convert(
+                "replacer::rust_trait!(replace; pub trait Placeholder<T>: Clone where T: Copy { fn foo(&self) -> u8 { if true { 1 } else { 2 } } });"
+            )?,
+            "pub trait Shape {}"
+        );
+        assert_eq!(
+            TraitRule::new(
+                "replace",
+                r#"trait Named { fn name() -> &'static str { "$name $1" } }"#
+            )?
+            .convert("replacer::rust_trait!(replace; trait Placeholder {});")?,
+            r#"trait Named { fn name() -> &'static str { "$name $1" } }"#
+        );
+        assert_eq!(
+            TraitRule::new("replace", "trait Shape {}")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
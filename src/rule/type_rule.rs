@@ -1,7 +1,12 @@
-use anyhow::Result;
-use regex::Regex;
+use std::fmt;
 
-use crate::Rule;
+use anyhow::{bail, Result};
+use regex::{Captures, Regex};
+
+use crate::{
+    rule::{comments, tokens_pattern},
+    Rule, RuleSpec,
+};
 
 /// Template macro for replacing a Rust type with a placeholder type that can be compiled.
 ///
@@ -17,6 +22,11 @@ macro_rules! rust_type {
 }
 
 /// Replace a Rust type.
+///
+/// The placeholder type can be any type, including fully-qualified paths, trait objects,
+/// function pointers and arrays like `[u8; 4]`.
+///
+/// Macros inside `//` and `/* .. */` comments are skipped.
 /// ```rust
 /// # use replacer::rule::{Rule, TypeRule};
 /// # fn main() -> anyhow::Result<()> {
@@ -25,7 +35,10 @@ macro_rules! rust_type {
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct TypeRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
     /// What the keyword will be replaced with.
     replace_with: String,
     /// Regex used to find the macro.
@@ -33,24 +46,191 @@ pub struct TypeRule {
 }
 
 impl Rule for TypeRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
     fn convert(&self, template: &str) -> Result<String> {
-        let replace_with: &str = &self.replace_with;
-        let replace = self.regex.replace_all(template, replace_with);
+        let comments = comments(template);
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            // Don't touch macros inside comments
+            let start = caps.get(0).map_or(0, |cap| cap.start());
+            if comments.iter().any(|comment| comment.contains(&start)) {
+                caps[0].to_string()
+            } else {
+                self.replace_with.clone()
+            }
+        });
 
         Ok(replace.into_owned())
     }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.count_matches(template) > 0
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        let comments = comments(template);
+
+        self.regex
+            .find_iter(template)
+            .filter(|found| {
+                !comments
+                    .iter()
+                    .any(|comment| comment.contains(&found.start()))
+            })
+            .count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Type {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for TypeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypeRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
 }
 
 impl TypeRule {
     /// Setup a new rule.
     pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
-        let regex = Regex::new(&format!(r"replacer::rust_type!\({};[^;]+;\)", matches))?;
+        let regex = Regex::new(&format!(
+            r"replacer::rust_type!\s*[\({{]{};{};\s*[\)}}]",
+            matches,
+            tokens_pattern()
+        ))?;
 
         Ok(Self {
+            matches: matches.to_string(),
             replace_with: replace_with.to_string(),
             regex,
         })
     }
+
+    /// Setup a new rule where the replacement is a type with generic parameters.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, TypeRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = TypeRule::new_with_generics("map", "HashMap", &["String", "Vec<u8>"])?;
+    /// assert_eq!(
+    ///     rule.convert("<replacer::rust_type!(map; Vec<i32>;)>::new()")?,
+    ///     "<HashMap<String, Vec<u8>>>::new()"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_generics(matches: &str, replace_with: &str, generics: &[&str]) -> Result<Self> {
+        generics
+            .iter()
+            .fold(
+                Self::builder().matches(matches).replace_with(replace_with),
+                |builder, generic| builder.generic(generic),
+            )
+            .build()
+    }
+
+    /// Start building a new rule step by step.
+    ///
+    /// ```rust
+    /// # use replacer::rule::{Rule, TypeRule};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let rule = TypeRule::builder()
+    ///     .matches("T")
+    ///     .replace_with("Vec")
+    ///     .generic("String")
+    ///     .build()?;
+    /// assert_eq!(rule.convert("<replacer::rust_type!(T; i32;)>::new()")?, "<Vec<String>>::new()");
+    ///
+    /// // The angle brackets of the replacement must be balanced
+    /// assert!(TypeRule::builder().matches("T").replace_with("Vec<String").build().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> TypeRuleBuilder {
+        TypeRuleBuilder::default()
+    }
+}
+
+/// Builder for the [`TypeRule`] struct.
+#[derive(Debug, Default, Clone)]
+pub struct TypeRuleBuilder {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// The type without generic parameters.
+    replace_with: String,
+    /// Generic parameters that will be added to the type.
+    generics: Vec<String>,
+}
+
+impl TypeRuleBuilder {
+    /// The name of the macro that will be matched with.
+    pub fn matches(mut self, matches: &str) -> Self {
+        self.matches = matches.to_string();
+
+        self
+    }
+
+    /// The type the macro will be replaced with.
+    pub fn replace_with(mut self, replace_with: &str) -> Self {
+        self.replace_with = replace_with.to_string();
+
+        self
+    }
+
+    /// Add a generic parameter to the type, they are added in order between angle brackets.
+    pub fn generic(mut self, generic: &str) -> Self {
+        self.generics.push(generic.to_string());
+
+        self
+    }
+
+    /// Create the [`TypeRule`], failing when the angle brackets of the type aren't balanced.
+    pub fn build(self) -> Result<TypeRule> {
+        if self.matches.is_empty() {
+            bail!("type rule is missing the name of the macro to match");
+        }
+
+        let replace_with = if self.generics.is_empty() {
+            self.replace_with
+        } else {
+            format!("{}<{}>", self.replace_with, self.generics.join(", "))
+        };
+        validate_angle_brackets(&replace_with)?;
+
+        TypeRule::new(&self.matches, &replace_with)
+    }
+}
+
+/// Check whether all angle brackets in a type are closed, ignoring the arrows of function types.
+fn validate_angle_brackets(ty: &str) -> Result<()> {
+    let mut depth = 0i32;
+    for (index, character) in ty.char_indices() {
+        match character {
+            '<' => depth += 1,
+            '>' if !ty[..index].ends_with('-') => {
+                depth -= 1;
+                if depth < 0 {
+                    bail!("unexpected `>` at byte {} in type `{}`", index, ty);
+                }
+            }
+            _ => (),
+        }
+    }
+    if depth != 0 {
+        bail!("unclosed `<` in type `{}`", ty);
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -66,15 +246,41 @@ mod tests {
                 .convert("let some_type = <replacer::rust_type!(replace; String;)>::new();")?,
             "let some_type = <i32>::new();"
         );
+        assert_eq!(
+            TypeRule::new("replace", "i32")?.convert(
+                "type A = replacer::rust_type!(replace; std::string::String;);\ntype B = replacer::rust_type!(replace; Box<dyn std::ops::Fn(i32) -> i32>;);\ntype C = replacer::rust_type!(replace; Box<dyn Iterator<Item = u8> + Send>;);\ntype D = replacer::rust_type!(replace; fn(&[u8; 4]) -> (u8, u8);) ;\ntype E = replacer::rust_type!(replace; <Vec<u8> as IntoIterator>::Item;);"
+            )?,
+            "type A = i32;\ntype B = i32;\ntype C = i32;\ntype D = i32 ;\ntype E = i32;"
+        );
         assert_eq!(
             TypeRule::new("replace", "i32")?.convert("Hello world!")?,
             "Hello world!"
         );
+        assert_eq!(
+            TypeRule::new("replace", "i32")?.convert(
+                "// <replacer::rust_type!(replace; String;)>\nlet a: replacer::rust_type!(replace; String;) = 0; /* replacer::rust_type!(replace; String;)\n */ let b = \"// \"; let c: replacer::rust_type!(replace; String;) = 0;"
+            )?,
+            "// <replacer::rust_type!(replace; String;)>\nlet a: i32 = 0; /* replacer::rust_type!(replace; String;)\n */ let b = \"// \"; let c: i32 = 0;"
+        );
+        assert!(!TypeRule::new("replace", "i32")?
+            .matches_any("/// <replacer::rust_type!(replace; String;)>::new()"));
         assert_eq!(
             TypeRule::new("replace", "i32")?
                 .convert("let some_type = Map<replacer::rust_type!(replace; String;), replacer::rust_type!(replace; String;)>::new();")?,
             "let some_type = Map<i32, i32>::new();"
         );
+        assert_eq!(
+            TypeRule::new("replace", "i32")?
+                .convert("let some_type = <replacer::rust_type! {replace; String;}>::new();")?,
+            "let some_type = <i32>::new();"
+        );
+        assert_eq!(
+            TypeRule::new_with_generics("replace", "Box", &["dyn Fn() -> Vec<u8>"])?
+                .convert("<replacer::rust_type!(replace; String;)>::new()")?,
+            "<Box<dyn Fn() -> Vec<u8>>>::new()"
+        );
+        assert!(TypeRule::new_with_generics("replace", "Vec<", &["u8"]).is_err());
+        assert!(TypeRule::builder().replace_with("i32").build().is_err());
 
         Ok(())
     }
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+
+use crate::{Rule, RuleSpec};
+
+/// Template macro for replacing a Rust import, the placeholder doesn't import anything.
+///
+/// ```rust
+/// // Private
+/// replacer::rust_use!(replace_with_use; std::vec::Vec;);
+/// // Public
+/// replacer::rust_use!(pub replace_with_other_use; std::collections::*;);
+/// ```
+#[macro_export]
+macro_rules! rust_use {
+    ($($_placeholder:tt)*) => {};
+}
+
+/// Replace a Rust import.
+///
+/// Glob and aliased imports can be used in the replacement.
+/// ```rust
+/// # use replacer::rule::{Rule, UseRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = UseRule::new("import", "std::collections::HashMap as Map")?;
+/// assert_eq!(rule.convert("replacer::rust_use! {import; std::vec::Vec;}")?,
+///     "use std::collections::HashMap as Map;");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct UseRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for UseRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace_with: &str = &self.replace_with;
+        let replace = self.regex.replace_all(template, |caps: &Captures| {
+            format!(
+                "{}use {};",
+                &caps.name("pub").map_or("", |cap| cap.as_str()),
+                replace_with,
+            )
+        });
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Use {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for UseRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UseRule({:?} -> {:?})", self.matches, self.replace_with)
+    }
+}
+
+impl UseRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_use!\s*[\({{](?P<pub>pub )?{};[^;]+;[\)}}];?",
+            matches
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn use_rule() -> Result<()> {
+        assert_eq!(
+            UseRule::new("replace", "std::collections::HashMap")?
+                .convert("replacer::rust_use! {replace; std::vec::Vec;}")?,
+            "use std::collections::HashMap;"
+        );
+        assert_eq!(
+            UseRule::new("replace", "std::collections::*")?
+                .convert("replacer::rust_use! {pub replace; std::{vec::Vec, fmt};}")?,
+            "pub use std::collections::*;"
+        );
+        assert_eq!(
+            UseRule::new("replace", "std::collections::HashMap")?
+                .convert("replacer::rust_use!(replace; std::vec::Vec;);\nfn main() {}")?,
+            "use std::collections::HashMap;\nfn main() {}"
+        );
+        assert_eq!(
+            UseRule::new("replace", "std::collections::HashMap")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use semver::Version;
+
+use crate::{rule::StringRule, Rule, RuleSpec};
+
+/// Replace a keyword with a SemVer version.
+///
+/// The keyword is matched like [`StringRule`], the version is validated when the rule is
+/// created.
+/// ```rust
+/// # use replacer::rule::{Rule, VersionRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = VersionRule::new("crate_version", "1.2.3")?;
+/// assert_eq!(rule.convert("replacer = \"$$crate_version$$\"")?, "replacer = \"1.2.3\"");
+///
+/// assert!(VersionRule::new("crate_version", "1.2").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct VersionRule {
+    /// The version the keyword will be replaced with.
+    version: String,
+    /// Rule used to replace the keyword.
+    rule: StringRule,
+}
+
+impl Rule for VersionRule {
+    fn name(&self) -> &str {
+        self.rule.name()
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        self.rule.convert(template)
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.rule.matches_any(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.rule.count_matches(template)
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Version {
+            matches: self.name().to_string(),
+            replace_with: self.version.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for VersionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VersionRule({:?} -> {:?})", self.name(), self.version)
+    }
+}
+
+impl VersionRule {
+    /// Setup a new rule, failing when the replacement isn't a valid SemVer version.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let version = Version::parse(replace_with.trim())
+            .with_context(|| format!("`{}` is not a valid SemVer version", replace_with))?
+            .to_string();
+
+        Ok(Self {
+            rule: StringRule::new(matches, &version)?,
+            version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn version_rule() -> Result<()> {
+        assert_eq!(
+            VersionRule::new("version", " 1.0.0-alpha.1+build.5 ")?
+                .convert("version = \"$$version$$\"")?,
+            "version = \"1.0.0-alpha.1+build.5\""
+        );
+        assert_eq!(
+            VersionRule::new("version", "0.4.1")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+        assert!(VersionRule::new("version", "^1.2.3").is_err());
+        assert!(VersionRule::new("version", "v1.2.3").is_err());
+        assert!(VersionRule::new("a version", "1.2.3").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use anyhow::Result;
+use regex::{NoExpand, Regex};
+
+use crate::{rule::tokens_pattern, Rule, RuleSpec};
+
+/// Template macro for replacing the where clause of a Rust item with a placeholder clause.
+///
+/// Rust doesn't allow macros in the position of a where clause, so unlike the other template
+/// macros a template using this can't be compiled before the rules are applied.
+///
+/// ```rust,compile_fail
+/// fn print<T>(value: T) replacer::rust_where!(replace_with_where; where T: std::fmt::Debug;) {
+///     println!("{:?}", value);
+/// }
+/// ```
+#[macro_export]
+macro_rules! rust_where {
+    ($_name:ident; where $($bounds:tt)*) => {};
+}
+
+/// Replace the where clause of a Rust item.
+///
+/// The replacement should start with the `where` keyword, the bounds can contain anything like
+/// `T: Iterator<Item = u8> + ?Sized` or `'a: 'b`.
+/// ```rust
+/// # use replacer::rule::{Rule, WhereRule};
+/// # fn main() -> anyhow::Result<()> {
+/// let rule = WhereRule::new("bounds", "where T: Serialize + DeserializeOwned")?;
+/// assert_eq!(
+///     rule.convert("fn load<T>() -> T replacer::rust_where!(bounds; where T: Clone;) {}")?,
+///     "fn load<T>() -> T where T: Serialize + DeserializeOwned {}"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WhereRule {
+    /// The name of the macro that will be matched with.
+    matches: String,
+    /// What the keyword will be replaced with.
+    replace_with: String,
+    /// Regex used to find the macro.
+    regex: Regex,
+}
+
+impl Rule for WhereRule {
+    fn name(&self) -> &str {
+        &self.matches
+    }
+
+    fn convert(&self, template: &str) -> Result<String> {
+        let replace = self
+            .regex
+            .replace_all(template, NoExpand(&self.replace_with));
+
+        Ok(replace.into_owned())
+    }
+
+    fn matches_any(&self, template: &str) -> bool {
+        self.regex.is_match(template)
+    }
+
+    fn count_matches(&self, template: &str) -> usize {
+        self.regex.find_iter(template).count()
+    }
+
+    fn to_spec(&self) -> Option<RuleSpec> {
+        Some(RuleSpec::Where {
+            matches: self.matches.clone(),
+            replace_with: self.replace_with.clone(),
+        })
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for WhereRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "WhereRule({:?} -> {:?})",
+            self.matches, self.replace_with
+        )
+    }
+}
+
+impl WhereRule {
+    /// Setup a new rule.
+    pub fn new(matches: &str, replace_with: &str) -> Result<Self> {
+        let regex = Regex::new(&format!(
+            r"replacer::rust_where!\s*[\({{]{};\s*where\b{};\s*[\)}}]",
+            matches,
+            tokens_pattern()
+        ))?;
+
+        Ok(Self {
+            matches: matches.to_string(),
+            replace_with: replace_with.to_string(),
+            regex,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn where_rule() -> Result<()> {
+        assert_eq!(
+            WhereRule::new("replace", "where T: Default")?.convert(
+                "impl<T> Point<T> replacer::rust_where!(replace; where T: Clone + ?Sized, U: Fn(&[u8; 4]) -> Box<dyn Iterator<Item = u8>>, 'a: 'b;) {}"
+            )?,
+            "impl<T> Point<T> where T: Default {}"
+        );
+        assert_eq!(
+            WhereRule::new("replace", "where T: Default")?.convert(
+                "fn a<T>() replacer::rust_where! {other; where T: Clone;} {}\nfn b<T>() replacer::rust_where! {replace; where T: Clone;} {}"
+            )?,
+            "fn a<T>() replacer::rust_where! {other; where T: Clone;} {}\nfn b<T>() where T: Default {}"
+        );
+        assert_eq!(
+            WhereRule::new("replace", "where $T: Clone")?
+                .convert("fn a<T>() replacer::rust_where!(replace; where T: Copy;) {}")?,
+            "fn a<T>() where $T: Clone {}"
+        );
+        assert_eq!(
+            WhereRule::new("replace", "where T: Default")?.convert("Hello world!")?,
+            "Hello world!"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,565 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rule::{
+        AttrRule, BlockRule, ChainRule, CompositeRule, ConditionalRule, ConstRule, EnumRule,
+        ExprRule, FailRule, FnBodyRule, FnRule, ImplRule, LifetimeRule, LineRule, LiteralRule,
+        MapRule, ModRule, NopRule, PathRule, RegexRule, Rule, SectionRule, StaticRule, StringRule,
+        StructRule, TraitRule, TypeRule, UseRule, VersionRule, WhereRule,
+    },
+    Template, TemplateBuilder, TemplateOptions,
+};
+
+/// Description of a built-in rule that can be serialized.
+///
+/// Every variant contains the arguments of the constructor of the rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RuleSpec {
+    /// A [`StringRule`].
+    String {
+        /// The keyword that will be matched with.
+        matches: String,
+        /// What the keyword will be replaced with.
+        replace_with: String,
+        /// Whether the rule was created with [`StringRule::new_case_insensitive`].
+        #[serde(default)]
+        case_insensitive: bool,
+        /// Whether the rule was created with [`StringRule::new_whole_word`].
+        #[serde(default)]
+        whole_word: bool,
+        /// The count passed to [`StringRule::new_counted`].
+        #[serde(default)]
+        count: Option<usize>,
+    },
+    /// A [`TypeRule`].
+    Type {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`StructRule`].
+    Struct {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+        /// What was passed to [`StructRule::keep_attributes`].
+        #[serde(default = "default_keep_attributes")]
+        keep_attributes: bool,
+    },
+    /// An [`EnumRule`].
+    Enum {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// An [`ExprRule`].
+    Expr {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`TraitRule`].
+    Trait {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`ConstRule`].
+    Const {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`StaticRule`].
+    Static {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// An [`ImplRule`].
+    Impl {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`ModRule`].
+    Mod {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`UseRule`].
+    Use {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`FnRule`].
+    Fn {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`LifetimeRule`].
+    Lifetime {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// An [`AttrRule`].
+    Attr {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`BlockRule`].
+    Block {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`FnBodyRule`].
+    FnBody {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`WhereRule`].
+    Where {
+        /// The name of the macro that will be matched with.
+        matches: String,
+        /// What the macro will be replaced with.
+        replace_with: String,
+    },
+    /// A [`LineRule`].
+    Line {
+        /// The name after `// REPLACE:` that will be matched with.
+        matches: String,
+        /// What the line will be replaced with.
+        replace_with: String,
+    },
+    /// A [`SectionRule`].
+    Section {
+        /// The name after `// BEGIN:` and `// END:` that will be matched with.
+        matches: String,
+        /// What the section will be replaced with.
+        replace_with: String,
+    },
+    /// A [`VersionRule`].
+    Version {
+        /// The keyword that will be matched with.
+        matches: String,
+        /// The version the keyword will be replaced with.
+        replace_with: String,
+    },
+    /// A [`PathRule`].
+    Path {
+        /// The keyword that will be matched with.
+        matches: String,
+        /// The path the keyword will be replaced with.
+        replace_with: String,
+    },
+    /// A [`LiteralRule`].
+    Literal {
+        /// The string that will be matched with.
+        from: String,
+        /// What the string will be replaced with.
+        to: String,
+    },
+    /// A [`MapRule`].
+    Map {
+        /// The keywords and what they will be replaced with.
+        map: HashMap<String, String>,
+    },
+    /// A [`RegexRule`].
+    Regex {
+        /// The regular expression.
+        pattern: String,
+        /// What the matches will be replaced with.
+        replace_with: String,
+    },
+    /// A [`ConditionalRule`].
+    Conditional {
+        /// The rule that must change the template.
+        condition: Box<RuleSpec>,
+        /// The rule that will be applied afterwards.
+        then_rule: Box<RuleSpec>,
+    },
+    /// A [`CompositeRule`].
+    Composite {
+        /// The rule that will be applied to the sections.
+        inner: Box<RuleSpec>,
+        /// Marker at the start of a section.
+        start_marker: String,
+        /// Marker at the end of a section.
+        end_marker: String,
+    },
+    /// A [`ChainRule`].
+    Chain {
+        /// Rules that will be applied in order.
+        rules: Vec<RuleSpec>,
+    },
+    /// A [`NopRule`].
+    Nop,
+    /// A [`FailRule`].
+    Fail {
+        /// The message of the error.
+        message: String,
+    },
+}
+
+/// Attributes are kept by [`StructRule`] unless told otherwise.
+fn default_keep_attributes() -> bool {
+    true
+}
+
+impl RuleSpec {
+    /// Create the rule described by this specification.
+    pub fn into_rule(self) -> Result<Box<dyn Rule>> {
+        Ok(match self {
+            RuleSpec::String {
+                matches,
+                replace_with,
+                case_insensitive,
+                whole_word,
+                count,
+            } => match (case_insensitive, whole_word, count) {
+                (false, false, None) => Box::new(StringRule::new(&matches, &replace_with)?),
+                (false, false, Some(count)) => {
+                    Box::new(StringRule::new_counted(&matches, &replace_with, count)?)
+                }
+                (true, false, None) => {
+                    Box::new(StringRule::new_case_insensitive(&matches, &replace_with)?)
+                }
+                (false, true, None) => {
+                    Box::new(StringRule::new_whole_word(&matches, &replace_with)?)
+                }
+                (true, true, _) => bail!(
+                    "string rule `{}` can't be both case insensitive and whole word",
+                    matches
+                ),
+                (_, _, Some(_)) => bail!(
+                    "string rule `{}` can't be both counted and case insensitive or whole word",
+                    matches
+                ),
+            },
+            RuleSpec::Type {
+                matches,
+                replace_with,
+            } => Box::new(TypeRule::new(&matches, &replace_with)?),
+            RuleSpec::Struct {
+                matches,
+                replace_with,
+                keep_attributes,
+            } => {
+                Box::new(StructRule::new(&matches, &replace_with)?.keep_attributes(keep_attributes))
+            }
+            RuleSpec::Enum {
+                matches,
+                replace_with,
+            } => Box::new(EnumRule::new(&matches, &replace_with)?),
+            RuleSpec::Expr {
+                matches,
+                replace_with,
+            } => Box::new(ExprRule::new(&matches, &replace_with)?),
+            RuleSpec::Trait {
+                matches,
+                replace_with,
+            } => Box::new(TraitRule::new(&matches, &replace_with)?),
+            RuleSpec::Const {
+                matches,
+                replace_with,
+            } => Box::new(ConstRule::new(&matches, &replace_with)?),
+            RuleSpec::Static {
+                matches,
+                replace_with,
+            } => Box::new(StaticRule::new(&matches, &replace_with)?),
+            RuleSpec::Impl {
+                matches,
+                replace_with,
+            } => Box::new(ImplRule::new(&matches, &replace_with)?),
+            RuleSpec::Mod {
+                matches,
+                replace_with,
+            } => Box::new(ModRule::new(&matches, &replace_with)?),
+            RuleSpec::Use {
+                matches,
+                replace_with,
+            } => Box::new(UseRule::new(&matches, &replace_with)?),
+            RuleSpec::Fn {
+                matches,
+                replace_with,
+            } => Box::new(FnRule::new(&matches, &replace_with)?),
+            RuleSpec::Lifetime {
+                matches,
+                replace_with,
+            } => Box::new(LifetimeRule::new(&matches, &replace_with)?),
+            RuleSpec::Attr {
+                matches,
+                replace_with,
+            } => Box::new(AttrRule::new(&matches, &replace_with)?),
+            RuleSpec::Block {
+                matches,
+                replace_with,
+            } => Box::new(BlockRule::new(&matches, &replace_with)?),
+            RuleSpec::FnBody {
+                matches,
+                replace_with,
+            } => Box::new(FnBodyRule::new(&matches, &replace_with)?),
+            RuleSpec::Where {
+                matches,
+                replace_with,
+            } => Box::new(WhereRule::new(&matches, &replace_with)?),
+            RuleSpec::Line {
+                matches,
+                replace_with,
+            } => Box::new(LineRule::new(&matches, &replace_with)?),
+            RuleSpec::Section {
+                matches,
+                replace_with,
+            } => Box::new(SectionRule::new(&matches, &replace_with)?),
+            RuleSpec::Version {
+                matches,
+                replace_with,
+            } => Box::new(VersionRule::new(&matches, &replace_with)?),
+            RuleSpec::Path {
+                matches,
+                replace_with,
+            } => Box::new(PathRule::new(&matches, &replace_with)?),
+            RuleSpec::Literal { from, to } => Box::new(LiteralRule::new(&from, &to)?),
+            RuleSpec::Map { map } => Box::new(MapRule::new(map)?),
+            RuleSpec::Regex {
+                pattern,
+                replace_with,
+            } => Box::new(RegexRule::new(&pattern, &replace_with)?),
+            RuleSpec::Conditional {
+                condition,
+                then_rule,
+            } => Box::new(ConditionalRule::new(
+                condition.into_rule()?,
+                then_rule.into_rule()?,
+            )?),
+            RuleSpec::Composite {
+                inner,
+                start_marker,
+                end_marker,
+            } => Box::new(CompositeRule::new(
+                inner.into_rule()?,
+                &start_marker,
+                &end_marker,
+            )?),
+            RuleSpec::Chain { rules } => Box::new(ChainRule::new(
+                rules
+                    .into_iter()
+                    .map(RuleSpec::into_rule)
+                    .collect::<Result<_>>()?,
+            )?),
+            RuleSpec::Nop => Box::new(NopRule::new()?),
+            RuleSpec::Fail { message } => Box::new(FailRule::new(&message)?),
+        })
+    }
+
+    /// The name of the described rule, the same as [`Rule::name`] of the created rule.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            RuleSpec::String { matches, .. }
+            | RuleSpec::Type { matches, .. }
+            | RuleSpec::Struct { matches, .. }
+            | RuleSpec::Enum { matches, .. }
+            | RuleSpec::Expr { matches, .. }
+            | RuleSpec::Trait { matches, .. }
+            | RuleSpec::Const { matches, .. }
+            | RuleSpec::Static { matches, .. }
+            | RuleSpec::Impl { matches, .. }
+            | RuleSpec::Mod { matches, .. }
+            | RuleSpec::Use { matches, .. }
+            | RuleSpec::Fn { matches, .. }
+            | RuleSpec::Lifetime { matches, .. }
+            | RuleSpec::Attr { matches, .. }
+            | RuleSpec::Block { matches, .. }
+            | RuleSpec::FnBody { matches, .. }
+            | RuleSpec::Where { matches, .. }
+            | RuleSpec::Line { matches, .. }
+            | RuleSpec::Section { matches, .. }
+            | RuleSpec::Version { matches, .. }
+            | RuleSpec::Path { matches, .. } => matches,
+            RuleSpec::Literal { from, .. } => from,
+            RuleSpec::Regex { pattern, .. } => pattern,
+            RuleSpec::Composite { inner, .. } => inner.name(),
+            RuleSpec::Conditional { condition, .. } => condition.name(),
+            RuleSpec::Chain { rules } => rules.first().map_or("unknown", RuleSpec::name),
+            RuleSpec::Map { map } => map.keys().min().map_or("unknown", String::as_str),
+            RuleSpec::Nop | RuleSpec::Fail { .. } => "unknown",
+        }
+    }
+
+    /// The type of the described rule, the same as [`Rule::rule_type`] of the created rule.
+    pub(crate) fn rule_type(&self) -> &'static str {
+        use std::any::type_name;
+
+        match self {
+            RuleSpec::String { .. } => type_name::<StringRule>(),
+            RuleSpec::Type { .. } => type_name::<TypeRule>(),
+            RuleSpec::Struct { .. } => type_name::<StructRule>(),
+            RuleSpec::Enum { .. } => type_name::<EnumRule>(),
+            RuleSpec::Expr { .. } => type_name::<ExprRule>(),
+            RuleSpec::Trait { .. } => type_name::<TraitRule>(),
+            RuleSpec::Const { .. } => type_name::<ConstRule>(),
+            RuleSpec::Static { .. } => type_name::<StaticRule>(),
+            RuleSpec::Impl { .. } => type_name::<ImplRule>(),
+            RuleSpec::Mod { .. } => type_name::<ModRule>(),
+            RuleSpec::Use { .. } => type_name::<UseRule>(),
+            RuleSpec::Fn { .. } => type_name::<FnRule>(),
+            RuleSpec::Lifetime { .. } => type_name::<LifetimeRule>(),
+            RuleSpec::Attr { .. } => type_name::<AttrRule>(),
+            RuleSpec::Block { .. } => type_name::<BlockRule>(),
+            RuleSpec::FnBody { .. } => type_name::<FnBodyRule>(),
+            RuleSpec::Where { .. } => type_name::<WhereRule>(),
+            RuleSpec::Line { .. } => type_name::<LineRule>(),
+            RuleSpec::Section { .. } => type_name::<SectionRule>(),
+            RuleSpec::Version { .. } => type_name::<VersionRule>(),
+            RuleSpec::Path { .. } => type_name::<PathRule>(),
+            RuleSpec::Literal { .. } => type_name::<LiteralRule>(),
+            RuleSpec::Map { .. } => type_name::<MapRule>(),
+            RuleSpec::Regex { .. } => type_name::<RegexRule>(),
+            RuleSpec::Conditional { .. } => type_name::<ConditionalRule>(),
+            RuleSpec::Composite { .. } => type_name::<CompositeRule>(),
+            RuleSpec::Chain { .. } => type_name::<ChainRule>(),
+            RuleSpec::Nop => type_name::<NopRule>(),
+            RuleSpec::Fail { .. } => type_name::<FailRule>(),
+        }
+    }
+}
+
+/// A [`Template`] that can be serialized, for example to cache it.
+///
+/// Only the built-in rules can be serialized, see [`RuleSpec`].
+///
+/// ```rust
+/// # use replacer::{rule::StringRule, SerializableTemplate, TemplateBuilder};
+/// # fn main() -> anyhow::Result<()> {
+/// let template = TemplateBuilder::new()
+///     .rule(StringRule::new("replace", "world")?)
+///     .build();
+///
+/// let json = serde_json::to_string(&SerializableTemplate::from_template(&template)?)?;
+/// let template = serde_json::from_str::<SerializableTemplate>(&json)?.into_template()?;
+///
+/// assert_eq!(template.apply("Hello $$replace$$")?, "Hello world");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializableTemplate {
+    /// The rules in the order they are applied.
+    pub rules: Vec<RuleSpec>,
+    /// The global settings of the template.
+    #[serde(default)]
+    pub options: TemplateOptions,
+}
+
+impl SerializableTemplate {
+    /// Describe all rules of the template.
+    ///
+    /// Fails when the template contains a rule that can't be serialized, like a custom rule or a
+    /// [`StringRule`] created with [`StringRule::new_with_fn`].
+    pub fn from_template(template: &Template) -> Result<Self> {
+        let rules = template
+            .rules
+            .iter()
+            .map(|rule| match rule.to_spec() {
+                Some(spec) => Ok(spec),
+                None => bail!(
+                    "rule '{}' of type {} can't be serialized",
+                    rule.name(),
+                    rule.rule_type()
+                ),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            rules,
+            options: *template.options(),
+        })
+    }
+
+    /// Create the template from the rules.
+    pub fn into_template(self) -> Result<Template> {
+        let rules = self
+            .rules
+            .into_iter()
+            .map(RuleSpec::into_rule)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = TemplateBuilder::with_capacity(rules.len());
+        builder.extend(rules);
+
+        Ok(builder.with_options(self.options).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn serializable_template() -> Result<()> {
+        let template = TemplateBuilder::new()
+            .rule(StringRule::new_case_insensitive("replace", "world")?)
+            .rule(TypeRule::new("replace_type", "i32")?)
+            .rule(ChainRule::new(vec![
+                Box::new(ConditionalRule::new(
+                    Box::new(StringRule::new_whole_word("first", "$$second$$")?),
+                    Box::new(StringRule::new("second", "1")?),
+                )?),
+                Box::new(NopRule::new()?),
+            ])?)
+            .rule(RegexRule::new(r"\d+", "number")?)
+            .fail_on_unresolved(true)
+            .build();
+
+        let serializable = SerializableTemplate::from_template(&template)?;
+        let json = serde_json::to_string(&serializable)?;
+        let deserialized: SerializableTemplate = serde_json::from_str(&json)?;
+        assert_eq!(deserialized, serializable);
+
+        // Converting back and forth doesn't lose anything
+        let converted = deserialized.into_template()?;
+        assert_eq!(
+            SerializableTemplate::from_template(&converted)?,
+            serializable
+        );
+        assert_eq!(
+            converted.apply("$$REPLACE$$ $$first$$ <replacer::rust_type!(replace_type; u8;)>")?,
+            template.apply("$$REPLACE$$ $$first$$ <replacer::rust_type!(replace_type; u8;)>")?
+        );
+
+        let template = TemplateBuilder::new()
+            .rule(StringRule::new_with_fn("replace", |name| name.to_string())?)
+            .build();
+        assert!(SerializableTemplate::from_template(&template).is_err());
+
+        Ok(())
+    }
+}
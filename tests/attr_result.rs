@@ -0,0 +1,13 @@
+#![cfg(feature = "macros")]
+#![allow(dead_code)]
+
+#[derive(Debug, Clone, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    println!("{:?}", point.clone());
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "macros")]
+
+use pretty_assertions::assert_eq;
+
+use replacer::{rule::AttrRule, TemplateBuilder};
+
+const ATTR_TEMPLATE: &str = include_str!("attr_template.rs");
+const ATTR_RESULT: &str = include_str!("attr_result.rs");
+
+#[test]
+fn test_attr() {
+    let template = TemplateBuilder::new()
+        .rule(AttrRule::new("derive_rule", "derive(Debug, Clone, PartialEq)").unwrap())
+        .build();
+
+    assert_eq!(template.apply(ATTR_TEMPLATE).unwrap(), ATTR_RESULT);
+}
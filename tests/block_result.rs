@@ -0,0 +1,7 @@
+fn main() {
+    let numbers = [1, 2, 3];
+
+    let total = { numbers.iter().product::<i32>() };
+
+    println!("{}", total);
+}
@@ -0,0 +1,13 @@
+fn main() {
+    let numbers = [1, 2, 3];
+
+    let total = replacer::rust_block!(sum_block; {
+        let mut total = 0;
+        for number in &numbers {
+            total += number;
+        }
+        total
+    };);
+
+    println!("{}", total);
+}
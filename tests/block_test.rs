@@ -0,0 +1,15 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{rule::BlockRule, TemplateBuilder};
+
+const BLOCK_TEMPLATE: &str = include_str!("block_template.rs");
+const BLOCK_RESULT: &str = include_str!("block_result.rs");
+
+#[test]
+fn test_block() {
+    let template = TemplateBuilder::new()
+        .rule(BlockRule::new("sum_block", "{ numbers.iter().product::<i32>() }").unwrap())
+        .build();
+
+    assert_eq!(template.apply(BLOCK_TEMPLATE).unwrap(), BLOCK_RESULT);
+}
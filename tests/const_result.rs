@@ -0,0 +1,7 @@
+const MAX_SIZE: usize = 1024;
+pub const MIN_SIZE: usize = 16;
+
+fn main() {
+    println!("{}", MAX_SIZE);
+    println!("{}", MIN_SIZE);
+}
@@ -0,0 +1,7 @@
+replacer::rust_const! {max_size; PLACEHOLDER: usize = 0;}
+replacer::rust_const!(pub min_size; MIN_PLACEHOLDER: usize = 0;);
+
+fn main() {
+    println!("{}", replacer::rust_expr!(max_size_name; PLACEHOLDER;));
+    println!("{}", replacer::rust_expr!(min_size_name; MIN_PLACEHOLDER;));
+}
@@ -0,0 +1,21 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{ConstRule, ExprRule},
+    TemplateBuilder,
+};
+
+const CONST_TEMPLATE: &str = include_str!("const_template.rs");
+const CONST_RESULT: &str = include_str!("const_result.rs");
+
+#[test]
+fn test_const() {
+    let template = TemplateBuilder::new()
+        .rule(ConstRule::new("max_size", "MAX_SIZE: usize = 1024").unwrap())
+        .rule(ExprRule::new("max_size_name", "MAX_SIZE").unwrap())
+        .rule(ConstRule::new("min_size", "MIN_SIZE: usize = 16").unwrap())
+        .rule(ExprRule::new("min_size_name", "MIN_SIZE").unwrap())
+        .build();
+
+    assert_eq!(template.apply(CONST_TEMPLATE).unwrap(), CONST_RESULT);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "proc-macro")]
+
+use pretty_assertions::assert_eq;
+
+#[derive(replacer::Template)]
+struct Greeting {
+    name: String,
+    times: usize,
+    r#type: &'static str,
+}
+
+#[derive(replacer::Template)]
+struct Wrapper<T: std::fmt::Display> {
+    value: T,
+}
+
+#[test]
+fn test_derive_template() {
+    let greeting = Greeting {
+        name: "world".to_string(),
+        times: 3,
+        r#type: "u8",
+    };
+    let template = greeting.template_builder().build();
+
+    assert_eq!(
+        template
+            .apply("Hello $$name$$ $$times$$ times as $$type$$")
+            .unwrap(),
+        "Hello world 3 times as u8"
+    );
+
+    let template = Wrapper { value: 1.5 }.template_builder().build();
+    assert_eq!(
+        template.apply("let x = $$value$$;").unwrap(),
+        "let x = 1.5;"
+    );
+}
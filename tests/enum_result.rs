@@ -0,0 +1,8 @@
+#![allow(dead_code)]
+
+#[rustfmt::skip]
+pub enum Shape { Circle(f32), Rectangle { width: f32, height: f32 } }
+
+fn main() {
+    let _shape = <Shape>::Circle(1.0);
+}
@@ -0,0 +1,8 @@
+#![allow(dead_code)]
+
+#[rustfmt::skip]
+replacer::rust_enum!{pub shape; Placeholder { Circle(f32), Square { size: f32 } };}
+
+fn main() {
+    let _shape = <replacer::rust_type!(shape; Placeholder;)>::Circle(1.0);
+}
@@ -0,0 +1,25 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{EnumRule, TypeRule},
+    TemplateBuilder,
+};
+
+const ENUM_TEMPLATE: &str = include_str!("enum_template.rs");
+const ENUM_RESULT: &str = include_str!("enum_result.rs");
+
+#[test]
+fn test_enum() {
+    let template = TemplateBuilder::new()
+        .rule(
+            EnumRule::new(
+                "shape",
+                "Shape { Circle(f32), Rectangle { width: f32, height: f32 } }",
+            )
+            .unwrap(),
+        )
+        .rule(TypeRule::new("shape", "Shape").unwrap())
+        .build();
+
+    assert_eq!(template.apply(ENUM_TEMPLATE).unwrap(), ENUM_RESULT);
+}
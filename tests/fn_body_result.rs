@@ -0,0 +1,11 @@
+fn sum(numbers: &[i32]) -> i32 {
+    let mut total = 0;
+    for number in numbers {
+        total += number;
+    }
+    total
+}
+
+fn main() {
+    println!("{}", sum(&[1, 2, 3]));
+}
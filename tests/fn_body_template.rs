@@ -0,0 +1,7 @@
+fn sum(numbers: &[i32]) -> i32 {
+    replacer::rust_fn_body!(sum_body; { numbers.len() as i32 };)
+}
+
+fn main() {
+    println!("{}", sum(&[1, 2, 3]));
+}
@@ -0,0 +1,21 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{rule::FnBodyRule, TemplateBuilder};
+
+const FN_BODY_TEMPLATE: &str = include_str!("fn_body_template.rs");
+const FN_BODY_RESULT: &str = include_str!("fn_body_result.rs");
+
+#[test]
+fn test_fn_body() {
+    let template = TemplateBuilder::new()
+        .rule(
+            FnBodyRule::new(
+                "sum_body",
+                "{\n    let mut total = 0;\n    for number in numbers {\n        total += number;\n    }\n    total\n}",
+            )
+            .unwrap(),
+        )
+        .build();
+
+    assert_eq!(template.apply(FN_BODY_TEMPLATE).unwrap(), FN_BODY_RESULT);
+}
@@ -0,0 +1,8 @@
+#[allow(dead_code)]
+trait Shape {
+    fn area(&self) -> f64;
+
+    fn name(&self) -> String;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[allow(dead_code)]
+trait Shape {
+    replacer::rust_fn! {area; fn placeholder(&self) -> i32;}
+
+    replacer::rust_fn!(name; fn placeholder_name(&self) -> &str;);
+}
+
+fn main() {}
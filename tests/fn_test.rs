@@ -0,0 +1,16 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{rule::FnRule, TemplateBuilder};
+
+const FN_TEMPLATE: &str = include_str!("fn_template.rs");
+const FN_RESULT: &str = include_str!("fn_result.rs");
+
+#[test]
+fn test_fn() {
+    let template = TemplateBuilder::new()
+        .rule(FnRule::new("area", "area(&self) -> f64").unwrap())
+        .rule(FnRule::new("name", "name(&self) -> String").unwrap())
+        .build();
+
+    assert_eq!(template.apply(FN_TEMPLATE).unwrap(), FN_RESULT);
+}
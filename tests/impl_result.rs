@@ -0,0 +1,15 @@
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn sum(&self) -> i32 {
+        self.x * self.y
+    }
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    println!("{}", point.sum());
+}
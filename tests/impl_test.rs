@@ -0,0 +1,21 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{rule::ImplRule, TemplateBuilder};
+
+const IMPL_TEMPLATE: &str = include_str!("impl_template.rs");
+const IMPL_RESULT: &str = include_str!("impl_result.rs");
+
+#[test]
+fn test_impl() {
+    let template = TemplateBuilder::new()
+        .rule(
+            ImplRule::new(
+                "point_impl",
+                "impl Point {\n    fn sum(&self) -> i32 {\n        self.x * self.y\n    }\n}",
+            )
+            .unwrap(),
+        )
+        .build();
+
+    assert_eq!(template.apply(IMPL_TEMPLATE).unwrap(), IMPL_RESULT);
+}
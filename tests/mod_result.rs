@@ -0,0 +1,10 @@
+mod storage {
+    pub fn name() -> &'static str {
+        "storage"
+    }
+}
+
+fn main() {
+    let name = storage::name();
+    println!("{}", name);
+}
@@ -0,0 +1,10 @@
+replacer::rust_mod! {storage; placeholder {
+    pub fn name() -> &'static str {
+        "storage"
+    }
+};}
+
+fn main() {
+    let name = replacer::rust_expr!(name; placeholder::name(););
+    println!("{}", name);
+}
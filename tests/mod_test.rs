@@ -0,0 +1,19 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{ExprRule, ModRule},
+    TemplateBuilder,
+};
+
+const MOD_TEMPLATE: &str = include_str!("mod_template.rs");
+const MOD_RESULT: &str = include_str!("mod_result.rs");
+
+#[test]
+fn test_mod() {
+    let template = TemplateBuilder::new()
+        .rule(ModRule::new("storage", "storage").unwrap())
+        .rule(ExprRule::new("name", "storage::name()").unwrap())
+        .build();
+
+    assert_eq!(template.apply(MOD_TEMPLATE).unwrap(), MOD_RESULT);
+}
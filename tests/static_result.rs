@@ -0,0 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(1);
+pub static LIMIT: usize = 10;
+
+fn main() {
+    COUNTER.fetch_add(1, Ordering::SeqCst);
+    println!("{}", LIMIT);
+}
@@ -0,0 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+replacer::rust_static! {counter; PLACEHOLDER: AtomicUsize = AtomicUsize::new(0);}
+replacer::rust_static!(pub limit; LIMIT_PLACEHOLDER: usize = 0;);
+
+fn main() {
+    replacer::rust_expr!(counter_name; PLACEHOLDER;).fetch_add(1, Ordering::SeqCst);
+    println!("{}", replacer::rust_expr!(limit_name; LIMIT_PLACEHOLDER;));
+}
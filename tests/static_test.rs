@@ -0,0 +1,21 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{ExprRule, StaticRule},
+    TemplateBuilder,
+};
+
+const STATIC_TEMPLATE: &str = include_str!("static_template.rs");
+const STATIC_RESULT: &str = include_str!("static_result.rs");
+
+#[test]
+fn test_static() {
+    let template = TemplateBuilder::new()
+        .rule(StaticRule::new("counter", "COUNTER: AtomicUsize = AtomicUsize::new(1)").unwrap())
+        .rule(ExprRule::new("counter_name", "COUNTER").unwrap())
+        .rule(StaticRule::new("limit", "LIMIT: usize = 10").unwrap())
+        .rule(ExprRule::new("limit_name", "LIMIT").unwrap())
+        .build();
+
+    assert_eq!(template.apply(STATIC_TEMPLATE).unwrap(), STATIC_RESULT);
+}
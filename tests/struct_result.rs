@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 #[rustfmt::skip]
 pub struct Point2D { x: i32, y: i32 }
 
@@ -25,6 +27,15 @@ impl<'a> Rectangle<'a> {
     }
 }
 
+struct Meters(f32);
+
+pub struct Unit;
+
+pub(crate) struct Identifier(u64);
+
+#[rustfmt::skip]
+struct Tuple<A, B> { first: A, second: B }
+
 fn main() {
     let pos = <Point2D>::new();
     let shape = <Rectangle>::new(&pos);
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 #[rustfmt::skip]
 replacer::rust_struct!{pub point; Point{ x: i32, y: i32};}
 
@@ -25,6 +27,15 @@ impl<'a> replacer::rust_type!(rectangle_lifetime; Square<'a>;) {
     }
 }
 
+replacer::rust_struct!(meters; Length(f64););
+
+replacer::rust_struct! {pub marker; Marker;}
+
+replacer::rust_struct!(pub(crate) id; Id(u32););
+
+#[rustfmt::skip]
+replacer::rust_struct!{pair; Pair<T: Clone, U>{ first: T, second: U };}
+
 fn main() {
     let pos = <replacer::rust_type!(point; Point;)>::new();
     let shape = <replacer::rust_type!(rectangle; Square;)>::new(&pos);
@@ -22,6 +22,10 @@ fn test_struct() {
         )
         .rule(TypeRule::new("rectangle", "Rectangle").unwrap())
         .rule(TypeRule::new("rectangle_lifetime", "Rectangle<'a>").unwrap())
+        .rule(StructRule::new("meters", "Meters(f32)").unwrap())
+        .rule(StructRule::new("marker", "Unit").unwrap())
+        .rule(StructRule::new("id", "Identifier(u64)").unwrap())
+        .rule(StructRule::new("pair", "Tuple<A, B> { first: A, second: B }").unwrap())
         .build();
 
     assert_eq!(template.apply(STRUCT_TEMPLATE).unwrap(), STRUCT_RESULT);
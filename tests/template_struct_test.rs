@@ -0,0 +1,35 @@
+#![cfg(feature = "macros")]
+
+use pretty_assertions::assert_eq;
+
+use replacer::TemplateBuilder;
+
+#[replacer::template_struct(name = "point")]
+#[derive(Debug, Default)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[replacer::template_struct(name = "wrapper")]
+pub struct Wrapper<'a, T>(&'a T);
+
+#[test]
+fn test_template_struct() {
+    assert_eq!(Point::TEMPLATE_RULE, "point");
+    assert_eq!(Wrapper::<i32>::TEMPLATE_RULE, "wrapper");
+    assert_eq!(Point::default().x + Point::default().y, 0);
+    assert_eq!(*Wrapper(&1).0, 1);
+
+    let template = TemplateBuilder::new()
+        .rule(Point::template_rule("Point3D { x: i32, y: i32, z: i32 }").unwrap())
+        .rule(Wrapper::<i32>::template_rule("Wrapper(i32)").unwrap())
+        .build();
+
+    assert_eq!(
+        template
+            .apply("#[replacer::template_struct(name = \"point\")]\nstruct Point { x: i32 }\n#[replacer::template_struct(name = \"wrapper\")]\npub struct Wrapper<'a, T>(&'a T);")
+            .unwrap(),
+        "struct Point3D { x: i32, y: i32, z: i32 }\npub struct Wrapper(i32);"
+    );
+}
@@ -0,0 +1,14 @@
+#[rustfmt::skip]
+trait Shape { fn area(&self) -> f64 { 0.0 } }
+
+struct Square;
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        1.0
+    }
+}
+
+fn main() {
+    println!("{}", Square.area());
+}
@@ -0,0 +1,14 @@
+#[rustfmt::skip]
+replacer::rust_trait! {shape; trait Shape { fn area(&self) -> i32; }}
+
+struct Square;
+
+impl Shape for Square {
+    fn area(&self) -> replacer::rust_type!(area_type; i32;) {
+        replacer::rust_expr!(area; 1;)
+    }
+}
+
+fn main() {
+    println!("{}", Square.area());
+}
@@ -0,0 +1,20 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{ExprRule, TraitRule, TypeRule},
+    TemplateBuilder,
+};
+
+const TRAIT_TEMPLATE: &str = include_str!("trait_template.rs");
+const TRAIT_RESULT: &str = include_str!("trait_result.rs");
+
+#[test]
+fn test_trait() {
+    let template = TemplateBuilder::new()
+        .rule(TraitRule::new("shape", "trait Shape { fn area(&self) -> f64 { 0.0 } }").unwrap())
+        .rule(TypeRule::new("area_type", "f64").unwrap())
+        .rule(ExprRule::new("area", "1.0").unwrap())
+        .build();
+
+    assert_eq!(template.apply(TRAIT_TEMPLATE).unwrap(), TRAIT_RESULT);
+}
@@ -0,0 +1,6 @@
+use std::collections::BTreeMap as Map;
+
+fn main() {
+    let map = <Map<i32, i32>>::new();
+    println!("{:?}", map);
+}
@@ -0,0 +1,6 @@
+replacer::rust_use! {map; std::collections::HashMap;}
+
+fn main() {
+    let map = <replacer::rust_type!(map; std::collections::HashMap<i32, i32>;)>::new();
+    println!("{:?}", map);
+}
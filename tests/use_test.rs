@@ -0,0 +1,19 @@
+use pretty_assertions::assert_eq;
+
+use replacer::{
+    rule::{TypeRule, UseRule},
+    TemplateBuilder,
+};
+
+const USE_TEMPLATE: &str = include_str!("use_template.rs");
+const USE_RESULT: &str = include_str!("use_result.rs");
+
+#[test]
+fn test_use() {
+    let template = TemplateBuilder::new()
+        .rule(UseRule::new("map", "std::collections::BTreeMap as Map").unwrap())
+        .rule(TypeRule::new("map", "Map<i32, i32>").unwrap())
+        .build();
+
+    assert_eq!(template.apply(USE_TEMPLATE).unwrap(), USE_RESULT);
+}